@@ -0,0 +1,232 @@
+//! Analytic Hierarchy Process to derive priorities from pairwise comparisons of criteria.
+//!
+//! Comparisons use the Saaty scale: a value of 1 means both criteria are equally important,
+//! 9 means the first criterion is extremely more important than the second and 1/9 means the
+//! opposite. The priorities are the principal eigenvector of the reciprocal comparison matrix.
+
+use anyhow::Result;
+use serde::{Deserialize, Serialize};
+
+use crate::RecicionError;
+
+/// Consistency ratio above which the comparisons are considered inconsistent.
+pub const MAX_CONSISTENCY_RATIO: f64 = 0.1;
+
+/// Saaty's random consistency indices for matrices with up to 15 rows.
+const RANDOM_INDEX: [f64; 15] = [
+    0.0, 0.0, 0.58, 0.90, 1.12, 1.24, 1.32, 1.41, 1.45, 1.49, 1.51, 1.48, 1.56, 1.57, 1.59,
+];
+
+const MAX_ITERATIONS: usize = 1000;
+const TOLERANCE: f64 = 1e-12;
+
+/// States that criterion `first` is `value` times as important as criterion `second`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Comparison {
+    pub first: String,
+    pub second: String,
+    pub value: f64,
+}
+
+impl Comparison {
+    pub fn new(first: &str, second: &str, value: f64) -> Result<Self> {
+        if !(1.0 / 9.0 - f64::EPSILON..=9.0 + f64::EPSILON).contains(&value) {
+            return Err(RecicionError::new(format!(
+                "comparison value {} is outside of the Saaty scale from 1/9 to 9",
+                value
+            ))
+            .into());
+        }
+
+        Ok(Self {
+            first: first.into(),
+            second: second.into(),
+            value,
+        })
+    }
+
+    /// Returns the value of the comparison of `first` with `second`, regardless of the order in
+    /// which the comparison was stored.
+    pub fn value_of(comparisons: &[Comparison], first: &str, second: &str) -> Option<f64> {
+        comparisons.iter().find_map(|c| {
+            if c.first == first && c.second == second {
+                Some(c.value)
+            } else if c.first == second && c.second == first {
+                Some(1.0 / c.value)
+            } else {
+                None
+            }
+        })
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct AhpResult {
+    /// Priorities of the criteria, summing up to one.
+    pub priorities: Vec<f64>,
+    pub lambda_max: f64,
+    pub consistency_index: f64,
+    pub consistency_ratio: f64,
+}
+
+impl AhpResult {
+    pub fn is_consistent(&self) -> bool {
+        self.consistency_ratio <= MAX_CONSISTENCY_RATIO
+    }
+}
+
+/// Parses a value on the Saaty scale, given either as a number or as a fraction like `1/3`.
+pub fn parse_value(input: &str) -> Result<f64> {
+    let input = input.trim();
+    let value = match input.split_once('/') {
+        Some((numerator, denominator)) => {
+            numerator.trim().parse::<f64>()? / denominator.trim().parse::<f64>()?
+        }
+        None => input.parse::<f64>()?,
+    };
+
+    Ok(value)
+}
+
+/// Derives the priorities of `criteria` from the comparisons of every pair of them.
+pub fn evaluate(criteria: &[String], comparisons: &[Comparison]) -> Result<AhpResult> {
+    let n = criteria.len();
+    let mut matrix = vec![vec![1.0; n]; n];
+    for i in 0..n {
+        for j in (i + 1)..n {
+            let value = Comparison::value_of(comparisons, &criteria[i], &criteria[j]).ok_or(
+                RecicionError::new(format!(
+                    "missing comparison of {} and {}",
+                    criteria[i], criteria[j]
+                )),
+            )?;
+            matrix[i][j] = value;
+            matrix[j][i] = 1.0 / value;
+        }
+    }
+
+    let (priorities, lambda_max) = principal_eigenvector(&matrix);
+    let consistency_index = if n > 1 {
+        (lambda_max - n as f64) / (n as f64 - 1.0)
+    } else {
+        0.0
+    };
+    let random_index = RANDOM_INDEX[n.clamp(1, RANDOM_INDEX.len()) - 1];
+    let consistency_ratio = if random_index > 0.0 {
+        consistency_index / random_index
+    } else {
+        0.0
+    };
+
+    Ok(AhpResult {
+        priorities,
+        lambda_max,
+        consistency_index,
+        consistency_ratio,
+    })
+}
+
+/// Approximates the principal eigenvector, normalized to sum up to one, and its eigenvalue by
+/// power iteration.
+fn principal_eigenvector(matrix: &[Vec<f64>]) -> (Vec<f64>, f64) {
+    let n = matrix.len();
+    if n == 0 {
+        return (Vec::new(), 0.0);
+    }
+
+    let multiply = |vector: &[f64]| -> Vec<f64> {
+        matrix
+            .iter()
+            .map(|row| row.iter().zip(vector).map(|(a, v)| a * v).sum())
+            .collect()
+    };
+
+    let mut vector = vec![1.0 / n as f64; n];
+    for _ in 0..MAX_ITERATIONS {
+        let product = multiply(&vector);
+        let sum: f64 = product.iter().sum();
+        let next: Vec<f64> = product.iter().map(|v| v / sum).collect();
+        let change: f64 = next.iter().zip(&vector).map(|(a, b)| (a - b).abs()).sum();
+        vector = next;
+        if change < TOLERANCE {
+            break;
+        }
+    }
+
+    let product = multiply(&vector);
+    let lambda_max = product.iter().zip(&vector).map(|(p, v)| p / v).sum::<f64>() / n as f64;
+
+    (vector, lambda_max)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn names(names: &[&str]) -> Vec<String> {
+        names.iter().map(|name| name.to_string()).collect()
+    }
+
+    #[test]
+    fn test_parse_value() {
+        assert_eq!(3.0, parse_value("3").unwrap());
+        assert_eq!(0.25, parse_value(" 1/4 ").unwrap());
+        assert!(parse_value("three").is_err());
+    }
+
+    #[test]
+    fn test_comparison_out_of_scale() {
+        assert!(Comparison::new("Fun", "Useful", 10.0).is_err());
+        assert!(Comparison::new("Fun", "Useful", 0.1).is_err());
+        assert!(Comparison::new("Fun", "Useful", 1.0 / 9.0).is_ok());
+    }
+
+    #[test]
+    fn test_consistent_comparisons() {
+        let criteria = names(&["A", "B", "C"]);
+        let comparisons = vec![
+            Comparison::new("A", "B", 2.0).unwrap(),
+            Comparison::new("A", "C", 4.0).unwrap(),
+            Comparison::new("C", "B", 0.5).unwrap(),
+        ];
+
+        let result = evaluate(&criteria, &comparisons).unwrap();
+
+        let expected = [4.0 / 7.0, 2.0 / 7.0, 1.0 / 7.0];
+        for (e, p) in expected.iter().zip(&result.priorities) {
+            assert!((e - p).abs() < 1e-9);
+        }
+        assert!((result.lambda_max - 3.0).abs() < 1e-9);
+        assert!(result.consistency_ratio.abs() < 1e-9);
+        assert!(result.is_consistent());
+    }
+
+    #[test]
+    fn test_inconsistent_comparisons() {
+        let criteria = names(&["A", "B", "C"]);
+        let comparisons = vec![
+            Comparison::new("A", "B", 9.0).unwrap(),
+            Comparison::new("B", "C", 9.0).unwrap(),
+            Comparison::new("C", "A", 9.0).unwrap(),
+        ];
+
+        let result = evaluate(&criteria, &comparisons).unwrap();
+
+        assert!(result.consistency_ratio > MAX_CONSISTENCY_RATIO);
+        assert!(!result.is_consistent());
+    }
+
+    #[test]
+    fn test_missing_comparison() {
+        let criteria = names(&["A", "B", "C"]);
+        let comparisons = vec![Comparison::new("A", "B", 2.0).unwrap()];
+        assert!(evaluate(&criteria, &comparisons).is_err());
+    }
+
+    #[test]
+    fn test_single_criterion() {
+        let result = evaluate(&names(&["A"]), &[]).unwrap();
+        assert_eq!(vec![1.0], result.priorities);
+        assert_eq!(0.0, result.consistency_ratio);
+    }
+}
@@ -0,0 +1,715 @@
+//! Analyses of the ranking of the projects and of how robust and sensitive it is.
+
+use anyhow::Result;
+
+use crate::{scoring::ScoringMethod, RecicionError, Scenario, Weight, Workspace};
+
+/// Settings of the Monte Carlo robustness analysis.
+#[derive(Debug, Clone, PartialEq)]
+pub struct RobustnessOptions {
+    pub iterations: usize,
+    /// Seed of the random number generator, so that analyses can be reproduced.
+    pub seed: u64,
+    /// Largest relative change of a priority, e.g. 0.1 for ±10 %.
+    pub priority_variation: f64,
+    /// Largest absolute change of an exact weight. Uncertain weights are always drawn from their
+    /// own range instead.
+    pub weight_variation: f64,
+}
+
+impl Default for RobustnessOptions {
+    fn default() -> Self {
+        Self {
+            iterations: 1000,
+            seed: 0,
+            priority_variation: 0.1,
+            weight_variation: 0.0,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct RobustnessReport {
+    pub iterations: usize,
+    pub projects: Vec<String>,
+    /// How often each project reached each rank, `rank_counts[project][rank]` with rank 0
+    /// being the top. Tied projects share the ranks they take up equally, so counts can be
+    /// fractional.
+    pub rank_counts: Vec<Vec<f64>>,
+}
+
+impl RobustnessReport {
+    /// Probability that the project at `index` ranks first.
+    pub fn first_probability(&self, index: usize) -> f64 {
+        self.rank_probability(index, 0)
+    }
+
+    /// Probability that the project at `index` reaches the 0-based `rank`.
+    pub fn rank_probability(&self, index: usize, rank: usize) -> f64 {
+        self.rank_counts[index][rank] / self.iterations as f64
+    }
+
+    /// Average 1-based rank of the project at `index`.
+    pub fn mean_rank(&self, index: usize) -> f64 {
+        self.rank_counts[index]
+            .iter()
+            .enumerate()
+            .map(|(rank, count)| (rank + 1) as f64 * count)
+            .sum::<f64>()
+            / self.iterations as f64
+    }
+}
+
+/// Ranks the projects many times with randomly perturbed priorities and weights, using the
+/// scoring method and the active priority set of the workspace.
+pub fn robustness(workspace: &Workspace, options: &RobustnessOptions) -> Result<RobustnessReport> {
+    if workspace.projects.is_empty() {
+        return Err(RecicionError::new("no projects in workspace".into()).into());
+    }
+    if options.iterations == 0 {
+        return Err(RecicionError::new("at least one iteration is needed".into()).into());
+    }
+
+    let method = workspace.get_scoring_method().scoring_method();
+    workspace.check_scoring_method(method)?;
+    let priorities = workspace
+        .active_priority_set()?
+        .priority_vector(&workspace.criteria);
+    let project_count = workspace.projects.len();
+
+    let grid = workspace.weight_grid();
+
+    let mut rng = SplitMix64::new(options.seed);
+    let mut rank_counts = vec![vec![0.0; project_count]; project_count];
+    for _ in 0..options.iterations {
+        let sampled_priorities: Vec<_> = priorities
+            .iter()
+            .map(|p| {
+                p * (1.0 + rng.uniform(-options.priority_variation, options.priority_variation))
+            })
+            .collect();
+        let sampled_weights: Vec<Vec<f64>> = grid
+            .iter()
+            .map(|weights| {
+                workspace
+                    .criteria
+                    .iter()
+                    .zip(weights)
+                    .map(|(criterion, weight)| {
+                        let value = match *weight {
+                            Weight::Exact(value) => {
+                                value
+                                    + rng.uniform(
+                                        -options.weight_variation,
+                                        options.weight_variation,
+                                    )
+                            }
+                            Weight::Range { min, max } => rng.uniform(min, max),
+                            Weight::Triangular { min, likely, max } => {
+                                rng.triangular(min, likely, max)
+                            }
+                        };
+                        let (min, max) = criterion.get_scale();
+                        let value = value
+                            .max(min.unwrap_or(f64::NEG_INFINITY))
+                            .min(max.unwrap_or(f64::INFINITY));
+                        criterion.orient(value)
+                    })
+                    .collect()
+            })
+            .collect();
+
+        let scores = method.score(&sampled_weights, &sampled_priorities)?;
+        let ranks = ranks_with_ties(&scores);
+        let mut tied = vec![0; project_count + 1];
+        for rank in &ranks {
+            tied[*rank] += 1;
+        }
+        for (project, rank) in ranks.into_iter().enumerate() {
+            let share = 1.0 / tied[rank] as f64;
+            for count in &mut rank_counts[project][rank - 1..rank - 1 + tied[rank]] {
+                *count += share;
+            }
+        }
+    }
+
+    Ok(RobustnessReport {
+        iterations: options.iterations,
+        projects: workspace.get_project_names(),
+        rank_counts,
+    })
+}
+
+/// Priority of a criterion at which another project draws level with the top-ranked one.
+#[derive(Debug, Clone, PartialEq)]
+pub struct BreakEven {
+    pub priority: f64,
+    /// Project that takes over the top rank beyond the break-even priority.
+    pub project: String,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct CriterionSensitivity {
+    pub criterion: String,
+    pub priority: f64,
+    /// Nearest break-even when lowering the priority, if there is any.
+    pub decrease: Option<BreakEven>,
+    /// Nearest break-even when raising the priority, if there is any.
+    pub increase: Option<BreakEven>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct SensitivityReport {
+    /// Top-ranked project under the current priorities.
+    pub leader: String,
+    pub criteria: Vec<CriterionSensitivity>,
+}
+
+/// Determines for each criterion of the active priority set how far its priority can change,
+/// with all other priorities fixed, before the top-ranked project changes.
+///
+/// The analysis is based on the weighted sum of the expected weights, where the scores change
+/// linearly with each priority.
+pub fn sensitivity(workspace: &Workspace) -> Result<SensitivityReport> {
+    if workspace.projects.is_empty() {
+        return Err(RecicionError::new("no projects in workspace".into()).into());
+    }
+
+    let (weights, priorities) = workspace.scoring_input(Scenario::Expected)?;
+    let scores: Vec<f64> = weights
+        .iter()
+        .map(|row| row.iter().zip(&priorities).map(|(w, p)| w * p).sum())
+        .collect();
+    let leader = ranking(&scores)[0];
+    let names = workspace.get_project_names();
+
+    let criteria = workspace
+        .criteria
+        .iter()
+        .enumerate()
+        .map(|(c, criterion)| {
+            // the gap to the leader closes by the difference of the weights per unit of priority
+            let break_even = |raising: bool| {
+                (0..weights.len())
+                    .filter(|project| *project != leader)
+                    .filter_map(|project| {
+                        let slope = weights[project][c] - weights[leader][c];
+                        let slope = if raising { slope } else { -slope };
+                        (slope > 0.0)
+                            .then(|| ((scores[leader] - scores[project]) / slope, slope, project))
+                    })
+                    .min_by(|a, b| a.0.total_cmp(&b.0).then(b.1.total_cmp(&a.1)))
+                    .map(|(change, _, project)| BreakEven {
+                        priority: if raising {
+                            priorities[c] + change
+                        } else {
+                            priorities[c] - change
+                        },
+                        project: names[project].clone(),
+                    })
+            };
+
+            CriterionSensitivity {
+                criterion: criterion.name.clone(),
+                priority: priorities[c],
+                decrease: break_even(false),
+                increase: break_even(true),
+            }
+        })
+        .collect();
+
+    Ok(SensitivityReport {
+        leader: names[leader].clone(),
+        criteria,
+    })
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct DominatedProject {
+    pub name: String,
+    /// Projects that are at least as good on every criterion and better on at least one.
+    pub dominated_by: Vec<String>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Dominance {
+    /// Projects that no other project dominates, in the order of the workspace.
+    pub pareto_front: Vec<String>,
+    pub dominated: Vec<DominatedProject>,
+}
+
+/// Finds the projects that are dominated by another project, independently of any priorities.
+///
+/// Uncertain weights only count as better if their whole range is, so a project dominates
+/// another one if its worst case is at least as good as the best case of the other project on
+/// every criterion.
+pub fn dominance(workspace: &Workspace) -> Dominance {
+    // oriented bounds of the weights, so that higher is always better
+    let bounds: Vec<Vec<(f64, f64)>> = workspace
+        .weight_grid()
+        .iter()
+        .map(|weights| {
+            workspace
+                .criteria
+                .iter()
+                .zip(weights)
+                .map(|(criterion, weight)| {
+                    let a = criterion.orient(weight.min());
+                    let b = criterion.orient(weight.max());
+                    (a.min(b), a.max(b))
+                })
+                .collect()
+        })
+        .collect();
+    let dominates = |a: usize, b: usize| {
+        let pairs = || bounds[a].iter().zip(&bounds[b]);
+        pairs().all(|((low, _), (_, high))| low >= high)
+            && pairs().any(|((low, _), (_, high))| low > high)
+    };
+
+    let names = workspace.get_project_names();
+    let mut pareto_front = Vec::new();
+    let mut dominated = Vec::new();
+    for (project, name) in names.iter().enumerate() {
+        let dominated_by: Vec<_> = (0..names.len())
+            .filter(|other| *other != project && dominates(*other, project))
+            .map(|other| names[other].clone())
+            .collect();
+        if dominated_by.is_empty() {
+            pareto_front.push(name.clone());
+        } else {
+            dominated.push(DominatedProject {
+                name: name.clone(),
+                dominated_by,
+            });
+        }
+    }
+
+    Dominance {
+        pareto_front,
+        dominated,
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct RankCorrelation {
+    pub first: String,
+    pub second: String,
+    /// Kendall tau-b of the scores under both priority sets, from -1 for reversed to 1 for
+    /// identical rankings. Undefined if all projects are tied under one of the sets.
+    pub kendall_tau: Option<f64>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct PrioritySetComparison {
+    pub priority_sets: Vec<String>,
+    pub projects: Vec<String>,
+    /// Scores of the projects under every priority set, `scores[project][priority_set]`, or
+    /// `None` if the priority set vetoes the project.
+    pub scores: Vec<Vec<Option<f64>>>,
+    /// 1-based ranks of the projects under every priority set, `ranks[project][priority_set]`,
+    /// or `None` if the priority set vetoes the project.
+    pub ranks: Vec<Vec<Option<usize>>>,
+    /// Rank correlation of every pair of priority sets, over the projects ranked under both.
+    pub correlations: Vec<RankCorrelation>,
+}
+
+/// Scores the projects under every priority set of the workspace side by side.
+///
+/// Projects vetoed by a priority set are left out before scoring under that set.
+pub fn compare_priority_sets(
+    workspace: &Workspace,
+    method: &dyn ScoringMethod,
+) -> Result<PrioritySetComparison> {
+    workspace.check_scoring_method(method)?;
+
+    let names = workspace.get_project_names();
+    let mut scores = vec![Vec::new(); names.len()];
+    let mut ranks = vec![Vec::new(); names.len()];
+    for priority_set in &workspace.priority_sets {
+        let pool = workspace.without_vetoed_for(priority_set);
+        let (weights, priorities) = pool.scoring_input_for(priority_set, Scenario::Expected);
+        let set_scores = method.score(&weights, &priorities)?;
+        let set_ranks = ranks_with_ties(&set_scores);
+
+        let pool_names = pool.get_project_names();
+        for (project, name) in names.iter().enumerate() {
+            let index = pool_names.iter().position(|pool_name| pool_name == name);
+            scores[project].push(index.map(|index| set_scores[index]));
+            ranks[project].push(index.map(|index| set_ranks[index]));
+        }
+    }
+
+    let priority_sets = workspace.get_priority_set_names();
+    let mut correlations = Vec::new();
+    for a in 0..priority_sets.len() {
+        for b in a + 1..priority_sets.len() {
+            // ranks instead of scores, so that projects count as tied like they are ranked
+            let (x, y): (Vec<_>, Vec<_>) = ranks
+                .iter()
+                .filter_map(|row| Some((row[a]? as f64, row[b]? as f64)))
+                .unzip();
+            correlations.push(RankCorrelation {
+                first: priority_sets[a].clone(),
+                second: priority_sets[b].clone(),
+                kendall_tau: kendall_tau(&x, &y),
+            });
+        }
+    }
+
+    Ok(PrioritySetComparison {
+        priority_sets,
+        projects: names,
+        scores,
+        ranks,
+        correlations,
+    })
+}
+
+/// Kendall tau-b rank correlation, which accounts for ties.
+fn kendall_tau(x: &[f64], y: &[f64]) -> Option<f64> {
+    let (mut concordant, mut discordant, mut pairs) = (0.0, 0.0, 0.0);
+    let (mut tied_x, mut tied_y) = (0.0, 0.0);
+    for i in 0..x.len() {
+        for j in i + 1..x.len() {
+            pairs += 1.0;
+            let a = (x[i] - x[j]).partial_cmp(&0.0)?;
+            let b = (y[i] - y[j]).partial_cmp(&0.0)?;
+            if a.is_eq() {
+                tied_x += 1.0;
+            }
+            if b.is_eq() {
+                tied_y += 1.0;
+            }
+            if a.is_ne() && b.is_ne() {
+                if a == b {
+                    concordant += 1.0;
+                } else {
+                    discordant += 1.0;
+                }
+            }
+        }
+    }
+
+    let denominator = f64::sqrt((pairs - tied_x) * (pairs - tied_y));
+    (denominator > 0.0).then(|| (concordant - discordant) / denominator)
+}
+
+/// Returns the indices of the projects from the highest to the lowest score, keeping the order
+/// of the workspace for ties.
+pub(crate) fn ranking(scores: &[f64]) -> Vec<usize> {
+    let mut indices: Vec<_> = (0..scores.len()).collect();
+    indices.sort_by(|a, b| scores[*b].total_cmp(&scores[*a]));
+    indices
+}
+
+/// Returns the 1-based rank of every project, where tied projects share the best of their ranks
+/// and the following ranks are skipped, e.g. 1, 2, 2, 4.
+///
+/// Scores that only differ by rounding errors count as tied.
+pub(crate) fn ranks_with_ties(scores: &[f64]) -> Vec<usize> {
+    let mut ranks = vec![0; scores.len()];
+    let order = ranking(scores);
+    for (position, project) in order.iter().enumerate() {
+        ranks[*project] = match position {
+            0 => 1,
+            _ if is_tie(scores[order[position - 1]], scores[*project]) => {
+                ranks[order[position - 1]]
+            }
+            _ => position + 1,
+        };
+    }
+    ranks
+}
+
+fn is_tie(a: f64, b: f64) -> bool {
+    (a - b).abs() <= 1e-9 * a.abs().max(b.abs()).max(1.0)
+}
+
+/// Small, seedable pseudo random number generator whose output doesn't depend on any external
+/// crate, so that seeds reproduce the same analysis across versions.
+struct SplitMix64 {
+    state: u64,
+}
+
+impl SplitMix64 {
+    fn new(seed: u64) -> Self {
+        Self { state: seed }
+    }
+
+    fn next_u64(&mut self) -> u64 {
+        self.state = self.state.wrapping_add(0x9e37_79b9_7f4a_7c15);
+        let mut z = self.state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+        z ^ (z >> 31)
+    }
+
+    /// Uniformly distributed number in `[0, 1)`.
+    fn next_f64(&mut self) -> f64 {
+        (self.next_u64() >> 11) as f64 / (1u64 << 53) as f64
+    }
+
+    fn uniform(&mut self, min: f64, max: f64) -> f64 {
+        min + (max - min) * self.next_f64()
+    }
+
+    /// Samples the triangular distribution by inverting its cumulative distribution function.
+    fn triangular(&mut self, min: f64, likely: f64, max: f64) -> f64 {
+        let width = max - min;
+        if width == 0.0 {
+            return min;
+        }
+
+        let u = self.next_f64();
+        let split = (likely - min) / width;
+        if u < split {
+            min + (u * width * (likely - min)).sqrt()
+        } else {
+            max - ((1.0 - u) * width * (max - likely)).sqrt()
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{scoring::WeightedSum, Criterion, Direction, Project};
+
+    fn build_test_workspace() -> Workspace {
+        let mut workspace = Workspace::new();
+        workspace
+            .add_project(Project::new("Clear winner"))
+            .unwrap()
+            .add_project(Project::new("Close call 1"))
+            .unwrap()
+            .add_project(Project::new("Close call 2"))
+            .unwrap();
+        workspace
+            .add_criterion(Criterion::new("Fun"))
+            .unwrap()
+            .add_criterion(Criterion::new("Useful"))
+            .unwrap();
+        workspace.add_priority_set("Workday").unwrap();
+        workspace.activate_priority_set("Workday").unwrap();
+
+        workspace.set_weight("Clear winner", "Fun", 10).unwrap();
+        workspace.set_weight("Clear winner", "Useful", 10).unwrap();
+        workspace.set_weight("Close call 1", "Fun", 3).unwrap();
+        workspace.set_weight("Close call 1", "Useful", 2).unwrap();
+        workspace.set_weight("Close call 2", "Fun", 2).unwrap();
+        workspace.set_weight("Close call 2", "Useful", 3).unwrap();
+
+        workspace
+    }
+
+    #[test]
+    fn test_robust_winner() {
+        let ws = build_test_workspace();
+        let report = robustness(&ws, &RobustnessOptions::default()).unwrap();
+
+        assert_eq!(1.0, report.first_probability(0));
+        assert_eq!(1.0, report.mean_rank(0));
+
+        let second_rank = report.rank_probability(1, 1) + report.rank_probability(2, 1);
+        assert_eq!(1.0, second_rank);
+        assert!(report.rank_probability(1, 1) > 0.2);
+        assert!(report.rank_probability(2, 1) > 0.2);
+    }
+
+    #[test]
+    fn test_uncertain_weights_change_the_winner() {
+        let mut ws = build_test_workspace();
+        ws.set_weight("Close call 1", "Useful", Weight::range(0.0, 40.0).unwrap())
+            .unwrap();
+
+        let report = robustness(&ws, &RobustnessOptions::default()).unwrap();
+
+        assert!(report.first_probability(0) > 0.2);
+        assert!(report.first_probability(1) > 0.2);
+        assert_eq!(0.0, report.first_probability(2));
+    }
+
+    #[test]
+    fn test_seed_reproduces_analysis() {
+        let ws = build_test_workspace();
+        let options = RobustnessOptions {
+            iterations: 200,
+            seed: 7,
+            priority_variation: 0.5,
+            weight_variation: 2.0,
+        };
+
+        let report = robustness(&ws, &options).unwrap();
+        assert_eq!(report, robustness(&ws, &options).unwrap());
+
+        let counts: f64 = report.rank_counts.iter().map(|counts| counts[0]).sum();
+        assert_eq!(200.0, counts);
+    }
+
+    #[test]
+    fn test_tied_projects_share_ranks() {
+        let mut ws = build_test_workspace();
+        ws.set_weight("Close call 2", "Fun", 3).unwrap();
+        ws.set_weight("Close call 2", "Useful", 2).unwrap();
+
+        let report = robustness(&ws, &RobustnessOptions::default()).unwrap();
+
+        assert_eq!(1.0, report.first_probability(0));
+        for project in [1, 2] {
+            assert_eq!(0.5, report.rank_probability(project, 1));
+            assert_eq!(0.5, report.rank_probability(project, 2));
+            assert_eq!(2.5, report.mean_rank(project));
+        }
+    }
+
+    #[test]
+    fn test_triangular_samples() {
+        let mut rng = SplitMix64::new(1);
+        let samples: Vec<_> = (0..10000).map(|_| rng.triangular(1.0, 2.0, 6.0)).collect();
+
+        assert!(samples.iter().all(|s| (1.0..=6.0).contains(s)));
+        let mean = samples.iter().sum::<f64>() / samples.len() as f64;
+        assert!((mean - 3.0).abs() < 0.1);
+    }
+
+    #[test]
+    fn test_sensitivity() {
+        let ws = build_test_workspace();
+        let report = sensitivity(&ws).unwrap();
+
+        assert_eq!("Clear winner", report.leader);
+        assert_eq!(2, report.criteria.len());
+
+        let fun = &report.criteria[0];
+        assert_eq!("Fun", fun.criterion);
+        assert_eq!(1.0, fun.priority);
+        assert_eq!(None, fun.increase);
+        let decrease = fun.decrease.as_ref().unwrap();
+        assert_eq!("Close call 2", decrease.project);
+        assert!((decrease.priority - -0.875).abs() < 1e-10);
+
+        let useful = &report.criteria[1];
+        assert_eq!("Close call 1", useful.decrease.as_ref().unwrap().project);
+    }
+
+    #[test]
+    fn test_sensitivity_increase() {
+        let mut ws = build_test_workspace();
+        ws.set_weight("Close call 1", "Fun", 20).unwrap();
+        ws.set_weight("Close call 1", "Useful", 0).unwrap();
+        ws.set_priority("Useful", 2.0).unwrap();
+
+        let report = sensitivity(&ws).unwrap();
+
+        assert_eq!("Clear winner", report.leader);
+        let fun = &report.criteria[0];
+        let increase = fun.increase.as_ref().unwrap();
+        assert_eq!("Close call 1", increase.project);
+        assert!((increase.priority - 2.0).abs() < 1e-10);
+        assert!(report.criteria[1].increase.is_none());
+    }
+
+    #[test]
+    fn test_dominance() {
+        let ws = build_test_workspace();
+        let result = dominance(&ws);
+
+        assert_eq!(vec!["Clear winner"], result.pareto_front);
+        assert_eq!(2, result.dominated.len());
+        assert_eq!("Close call 1", result.dominated[0].name);
+        assert_eq!(vec!["Clear winner"], result.dominated[0].dominated_by);
+    }
+
+    #[test]
+    fn test_dominance_respects_direction() {
+        let mut ws = build_test_workspace();
+        ws.set_weight("Clear winner", "Fun", 1).unwrap();
+        ws.get_criterion("Fun")
+            .unwrap()
+            .set_direction(Direction::Cost);
+
+        let result = dominance(&ws);
+
+        assert_eq!(vec!["Clear winner"], result.pareto_front);
+        ws.get_criterion("Fun")
+            .unwrap()
+            .set_direction(Direction::Benefit);
+        let result = dominance(&ws);
+        assert_eq!(3, result.pareto_front.len());
+    }
+
+    #[test]
+    fn test_dominance_with_uncertain_weights() {
+        let mut ws = build_test_workspace();
+        ws.set_weight("Close call 1", "Fun", Weight::range(2.0, 12.0).unwrap())
+            .unwrap();
+
+        let result = dominance(&ws);
+
+        assert_eq!(vec!["Clear winner", "Close call 1"], result.pareto_front);
+        assert_eq!("Close call 2", result.dominated[0].name);
+    }
+
+    #[test]
+    fn test_compare_priority_sets() {
+        let mut ws = build_test_workspace();
+        ws.add_priority_set("Weekend").unwrap();
+        ws.activate_priority_set("Weekend").unwrap();
+        ws.set_priority("Useful", 0.0).unwrap();
+        ws.add_priority_set("Chores").unwrap();
+        ws.activate_priority_set("Chores").unwrap();
+        ws.set_priority("Fun", 0.0).unwrap();
+
+        let comparison = compare_priority_sets(&ws, &WeightedSum).unwrap();
+
+        assert_eq!(
+            vec!["Workday", "Weekend", "Chores"],
+            comparison.priority_sets
+        );
+        assert_eq!(
+            vec![Some(20.0), Some(10.0), Some(10.0)],
+            comparison.scores[0]
+        );
+        assert_eq!(vec![Some(2), Some(2), Some(3)], comparison.ranks[1]);
+        // the close calls are tied under the workday priorities and share the rank
+        assert_eq!(vec![Some(2), Some(3), Some(2)], comparison.ranks[2]);
+
+        let taus: Vec<_> = comparison
+            .correlations
+            .iter()
+            .map(|c| (c.first.as_str(), c.second.as_str(), c.kendall_tau.unwrap()))
+            .collect();
+        assert_eq!(3, taus.len());
+        // the close calls are tied under the workday priorities
+        assert!((taus[0].2 - 2.0 / 6f64.sqrt()).abs() < 1e-10);
+        assert_eq!(("Weekend", "Chores"), (taus[2].0, taus[2].1));
+        assert!((taus[2].2 - 1.0 / 3.0).abs() < 1e-10);
+    }
+
+    #[test]
+    fn test_ranks_with_ties() {
+        assert_eq!(vec![2, 1, 2, 4], ranks_with_ties(&[3.0, 5.0, 3.0, 1.0]));
+        assert_eq!(vec![1, 1], ranks_with_ties(&[0.1 + 0.2, 0.3]));
+        assert!(ranks_with_ties(&[]).is_empty());
+    }
+
+    #[test]
+    fn test_kendall_tau() {
+        assert_eq!(Some(1.0), kendall_tau(&[1.0, 2.0, 3.0], &[2.0, 4.0, 8.0]));
+        assert_eq!(Some(-1.0), kendall_tau(&[1.0, 2.0, 3.0], &[3.0, 2.0, 1.0]));
+        assert_eq!(None, kendall_tau(&[1.0, 1.0], &[1.0, 2.0]));
+        assert_eq!(None, kendall_tau(&[1.0], &[1.0]));
+    }
+
+    #[test]
+    fn test_no_iterations() {
+        let ws = build_test_workspace();
+        let options = RobustnessOptions {
+            iterations: 0,
+            ..Default::default()
+        };
+        assert!(robustness(&ws, &options).is_err());
+        assert!(robustness(&Workspace::new(), &RobustnessOptions::default()).is_err());
+    }
+}
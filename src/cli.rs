@@ -1,4 +1,4 @@
-use clap::{arg, Command};
+use clap::{arg, value_parser, Arg, ArgGroup, Command};
 
 pub fn build_cli() -> Command {
     Command::new("recision")
@@ -27,6 +27,12 @@ pub fn build_cli() -> Command {
                     Command::new("deactivate")
                         .about("Deactivates a workspace")
                         .alias("d")
+                )
+                .subcommand(
+                    Command::new("migrate")
+                        .about("Upgrades the active workspace to the current file format")
+                        .arg(arg!(--check "Report the pending upgrades without changing the workspace"))
+                        .after_help("Older workspace files are also upgraded in memory whenever they are read, and written in the current format when they change.")
                 ),
         )
         .subcommand(
@@ -39,6 +45,7 @@ pub fn build_cli() -> Command {
                     Command::new("list")
                         .about("List the projects in the pool")
                         .aliases(["l", "ls"])
+                        .arg(arg!(--archived "Include archived projects"))
                 )
                 .subcommand(
                     Command::new("add")
@@ -51,12 +58,56 @@ pub fn build_cli() -> Command {
                         .about("Remove a project from the pool")
                         .aliases(["r", "rm"])
                         .arg(arg!(<PROJECT> ... "Names or IDs of the projects"))
+                        .arg(arg!(-n --"dry-run" "Print the resulting projects without changing the workspace"))
                 )
                 .subcommand(
                     Command::new("reorder")
                         .about("Reorder projects in the pool by changing project IDs")
                         .after_help("If the new order is incomplete, the specified projects are moved to the top of the order.")
                         .arg(arg!(<PROJECT> ... "Names or IDs of the projects"))
+                        .arg(arg!(-n --"dry-run" "Print the resulting order without changing the workspace"))
+                )
+                .subcommand(
+                    Command::new("depend")
+                        .about("Declare that a project is blocked by prerequisites")
+                        .arg(arg!(<PROJECT> "Name or ID of the project"))
+                        .arg(arg!(<PREREQUISITE> ... "Names or IDs of the projects that have to be done first"))
+                )
+                .subcommand(
+                    Command::new("undepend")
+                        .about("Remove prerequisites of a project")
+                        .arg(arg!(<PROJECT> "Name or ID of the project"))
+                        .arg(arg!(<PREREQUISITE> ... "Names or IDs of the prerequisites"))
+                )
+                .subcommand(
+                    Command::new("status")
+                        .about("Show or change the status of a project")
+                        .arg(arg!(<PROJECT> "Name or ID of the project"))
+                        .arg(
+                            arg!([STATUS] "New status of the project")
+                                .value_parser(["idea", "active", "paused", "done", "dropped"])
+                        )
+                        .after_help(concat!(
+                            "Without a new status, the current status and the history of status changes are shown.\n",
+                            "Done and dropped projects are left out of rankings, display includes them with --closed."
+                        ))
+                )
+                .subcommand(
+                    Command::new("archive")
+                        .about("Take projects out of the pool without removing them")
+                        .after_help("Archived projects are left out of listings and rankings, but keep their weights and history.")
+                        .arg(arg!(<PROJECT> ... "Names or IDs of the projects"))
+                )
+                .subcommand(
+                    Command::new("restore")
+                        .about("Return archived projects to the pool")
+                        .arg(arg!(<PROJECT> ... "Names or IDs of the projects"))
+                )
+                .subcommand(
+                    Command::new("deps")
+                        .about("Show the prerequisites of projects as a tree")
+                        .arg(arg!([PROJECT] "Name or ID of the project, defaults to all projects no other project depends on"))
+                        .arg(arg!(--ascii "Draw the tree with ASCII instead of Unicode characters"))
                 ),
         )
         .subcommand(
@@ -74,13 +125,25 @@ pub fn build_cli() -> Command {
                     Command::new("add")
                         .about("Add a new criterion")
                         .alias("a")
+                        .allow_negative_numbers(true)
                         .arg(arg!(<NAME> "Name of the new criterion"))
-                        .arg(arg!([PRIORITY] "Priority of the new criterion"))
+                        .arg(arg!([PRIORITY] "Priority of the new criterion").value_parser(value_parser!(f64)))
+                        .args(criterion_details())
                         .after_help(concat!(
                             "If the priority is not specified, it is set to 1.\n",
-                            "Criterion names must contain at least one alphabetic character."
+                            "Criterion names must contain at least one alphabetic character.\n",
+                            "Weights of cost criteria are inverted for scoring, so lower weights are better.\n",
+                            "Projects without a weight are scored with the worst weight of the criterion."
                         ))
                 )
+                .subcommand(
+                    Command::new("update")
+                        .about("Update the direction, scale, unit or description of a criterion")
+                        .alias("u")
+                        .allow_negative_numbers(true)
+                        .arg(arg!(<CRITERION> "Name or ID of the criterion"))
+                        .args(criterion_details())
+                )
                 .subcommand(
                     Command::new("remove")
                         .about("Remove a criterion")
@@ -91,21 +154,23 @@ pub fn build_cli() -> Command {
                     Command::new("reorder")
                         .about("Reorder the criteria by changing their IDs")
                         .after_help("If the new order is incomplete, the specified criteria are moved to the top of the order.")
-                        .arg(arg!(<CRITERIION> ... "Names or IDs of the criteria"))
+                        .arg(arg!(<CRITERION> ... "Names or IDs of the criteria"))
                 )
                 .subcommand(
                     Command::new("update-priority")
                         .about("Update the priority of a criterion")
                         .alias("up")
-                        .arg(arg!(<CRITERIION> "Name or ID of the criterion"))
-                        .arg(arg!(<PRIORITY> "New priority of the criterion"))
+                        .allow_negative_numbers(true)
+                        .arg(arg!(<CRITERION> "Name or ID of the criterion"))
+                        .arg(arg!(<PRIORITY> "New priority of the criterion").value_parser(value_parser!(f64)))
                 )
         )
         .subcommand(
             Command::new("priority-set")
-                .about("Manage prioritiy sets")
+                .about("Manage priority sets")
                 .alias("ps")
                 .subcommand_required(true)
+                .arg_required_else_help(true)
                 .subcommand(
                     Command::new("list")
                         .about("List priority sets")
@@ -121,7 +186,7 @@ pub fn build_cli() -> Command {
                     Command::new("remove")
                         .about("Remove a priority set")
                         .aliases(["rm", "r"])
-                        .arg(arg!([PS] ... "Names of IDs of the priority sets"))
+                        .arg(arg!(<PS> ... "Names or IDs of the priority sets"))
                 )
                 .subcommand(
                     Command::new("reorder")
@@ -134,6 +199,36 @@ pub fn build_cli() -> Command {
                         .about("Activate a priority set")
                         .arg(arg!(<PS> "Name or ID of the priority set"))
                 )
+                .subcommand(
+                    Command::new("compare")
+                        .about("Derive the priorities of a priority set from pairwise comparisons of the criteria")
+                        .arg(arg!([PS] "Name or ID of the priority set, defaults to the active one"))
+                        .arg(arg!(-f --force "Accept inconsistent comparisons with a warning"))
+                        .after_help(concat!(
+                            "Every pair of criteria is compared on the Saaty scale from 1/9 to 9, where 1 means equally\n",
+                            "important and 9 means the first criterion is extremely more important than the second.\n",
+                            "Comparisons with a consistency ratio above 0.1 are rejected unless --force is given."
+                        ))
+                )
+                .subcommand(
+                    Command::new("constrain")
+                        .about("Veto projects whose weight on a criterion is outside of the given bounds")
+                        .arg(arg!(<CRITERION> "Name or ID of the criterion"))
+                        .arg(arg!(--min <MIN> "Lowest acceptable weight").value_parser(value_parser!(f64)).allow_negative_numbers(true))
+                        .arg(arg!(--max <MAX> "Highest acceptable weight").value_parser(value_parser!(f64)).allow_negative_numbers(true))
+                        .arg(arg!(-s --set <PS> "Name or ID of the priority set, defaults to the active one"))
+                        .group(ArgGroup::new("bounds").args(["min", "max"]).required(true).multiple(true))
+                        .after_help(concat!(
+                            "Vetoed projects are left out before scoring, so they don't shift the scores of the others,\n",
+                            "and are listed separately by display. Uncertain weights are judged by their expected value."
+                        ))
+                )
+                .subcommand(
+                    Command::new("unconstrain")
+                        .about("Remove the constraint on a criterion")
+                        .arg(arg!(<CRITERION> "Name or ID of the criterion"))
+                        .arg(arg!(-s --set <PS> "Name or ID of the priority set, defaults to the active one"))
+                )
         )
         .subcommand(
             Command::new("weight")
@@ -152,14 +247,188 @@ pub fn build_cli() -> Command {
                     Command::new("set")
                         .about("Set the current weight of the given project and criterion")
                         .alias("s")
+                        .allow_negative_numbers(true)
                         .arg(arg!(<PROJECT> "Name or ID of the project"))
                         .arg(arg!(<CRITERION> "Name or ID of the criterion"))
-                        .arg(arg!(<WEIGHT> "Value of the weight"))
+                        .arg(arg!(<WEIGHT> "Value of the weight").allow_hyphen_values(true))
+                        .after_help(concat!(
+                            "Weights can be exact like 2.5, ranges like 1..4 or three-point estimates like 1..2..5,\n",
+                            "where the middle value is the most likely one."
+                        ))
                 )
         )
         .subcommand(
             Command::new("display")
                 .about("Display the grid of projects, criteria and weights")
                 .alias("d")
+                .arg(arg!(-t --truncate "Truncate names to fit the grid into the terminal"))
+                .arg(
+                    arg!(-w --width <COLUMNS> "Truncate names to fit the grid into the given width")
+                        .value_parser(value_parser!(usize))
+                )
+                .arg(arg!(--ascii "Draw the grid with ASCII instead of Unicode characters"))
+                .arg(
+                    arg!(-m --method <METHOD> "Scoring method, defaults to the method of the workspace")
+                        .value_parser(["weighted-sum", "weighted-product", "min-max", "z-score", "topsis"])
+                )
+                .arg(arg!(-a --"all-sets" "Show the scores and ranks under every priority set side by side"))
+                .arg(
+                    arg!(-d --dependencies <MODE> "Rank projects below their prerequisites, or let prerequisites inherit scores")
+                        .value_parser(["prerequisites", "inherit"])
+                        .conflicts_with("all-sets")
+                )
+                .arg(
+                    arg!(--share <FRACTION> "Share of the scores of dependents that prerequisites inherit")
+                        .value_parser(value_parser!(f64))
+                        .requires("dependencies")
+                )
+                .arg(arg!(--archived "Include archived projects in the ranking"))
+                .arg(arg!(--closed "Include done and dropped projects in the ranking"))
+                .after_help(concat!(
+                    "Projects are sorted by their score under the active priority set. With --all-sets, rank\n",
+                    "changes refer to the previous priority set and the rankings of every pair of priority sets\n",
+                    "are compared by their Kendall tau rank correlation. Projects with equal scores share a rank."
+                ))
+        )
+        .subcommand(
+            Command::new("explain")
+                .about("Explain the score of a project or compare the scores of two projects")
+                .alias("e")
+                .arg(arg!(<PROJECT> "Name or ID of the project"))
+                .arg(arg!([OTHER] "Name or ID of a project to compare with"))
+                .arg(
+                    arg!(-m --method <METHOD> "Scoring method, defaults to the method of the workspace")
+                        .value_parser(["weighted-sum", "min-max", "z-score"])
+                )
+                .after_help(concat!(
+                    "Scores are broken down into the contributions of the criteria under the active priority\n",
+                    "set. Scores of the weighted product and TOPSIS don't sum up over the criteria, so they\n",
+                    "can't be broken down."
+                ))
+        )
+        .subcommand(
+            Command::new("dominance")
+                .about("List the Pareto-optimal projects and the projects dominated by others")
+                .after_help(concat!(
+                    "A project dominates another one if it is at least as good on every criterion and\n",
+                    "better on at least one, independently of any priority set."
+                ))
+        )
+        .subcommand(
+            Command::new("portfolio")
+                .about("Select the projects with the highest total score that fit into a budget")
+                .arg(
+                    arg!(-b --budget <BUDGET> "Budget for the costs of the selected projects")
+                        .required(true)
+                        .value_parser(value_parser!(f64))
+                )
+                .arg(arg!(-c --criterion <CRITERION> "Name or ID of the criterion with the costs, defaults to the budget cost criterion"))
+                .arg(
+                    arg!(-a --alternatives <N> "Number of runner-up portfolios to show")
+                        .value_parser(value_parser!(usize))
+                        .default_value("3")
+                )
+                .after_help(concat!(
+                    "Costs are the weights of the projects on a criterion, which can be designated with\n",
+                    "criterion update <CRITERION> --budget-cost. Every project needs a cost. Vetoed projects and\n",
+                    "projects without a positive score are never selected."
+                ))
+        )
+        .subcommand(
+            Command::new("snapshot")
+                .about("Record the current scores under every priority set")
+                .arg(arg!(<LABEL> "Label of the snapshot, like the name of the sprint"))
+                .after_help(concat!(
+                    "Archived, done and dropped projects are left out of the snapshot, as are the projects\n",
+                    "that a priority set vetoes."
+                ))
+        )
+        .subcommand(
+            Command::new("trend")
+                .about("Show the scores and ranks of the projects across the snapshots")
+                .arg(arg!(-s --set <PRIORITY_SET> "Name or ID of the priority set, defaults to the active priority set"))
+                .arg(
+                    arg!(--movers <COUNT> "Number of biggest movers to list")
+                        .value_parser(value_parser!(usize))
+                        .default_value("3")
+                )
+                .after_help("Rank changes refer to the first and the last snapshot that include the project.")
         )
+        .subcommand(
+            Command::new("undo")
+                .about("Undo the latest change to the workspace")
+        )
+        .subcommand(
+            Command::new("redo")
+                .about("Redo the latest undone change to the workspace")
+        )
+        .subcommand(
+            Command::new("history")
+                .about("List the recorded changes to the workspace")
+                .after_help(concat!(
+                    "Changes are recorded in a journal next to the workspace file. Undoing and redoing a\n",
+                    "change is recorded as well, and new changes discard the undone ones."
+                ))
+        )
+        .subcommand(
+            Command::new("analyze")
+                .about("Analyze how robust the ranking of the projects is")
+                .alias("a")
+                .arg_required_else_help(true)
+                .subcommand(
+                    Command::new("robustness")
+                        .about("Rank the projects many times with randomly perturbed priorities and weights")
+                        .alias("r")
+                        .arg(
+                            arg!(-n --iterations <N> "Number of random rankings")
+                                .value_parser(value_parser!(usize))
+                                .default_value("1000")
+                        )
+                        .arg(
+                            arg!(--seed <SEED> "Seed of the random numbers, the same seed reproduces the analysis")
+                                .value_parser(value_parser!(u64))
+                                .default_value("0")
+                        )
+                        .arg(
+                            arg!(--"priority-variation" <FRACTION> "Largest relative change of the priorities")
+                                .value_parser(value_parser!(f64))
+                                .default_value("0.1")
+                        )
+                        .arg(
+                            arg!(--"weight-variation" <AMOUNT> "Largest absolute change of exact weights")
+                                .value_parser(value_parser!(f64))
+                                .default_value("0")
+                        )
+                        .arg(
+                            arg!(-m --method <METHOD> "Scoring method, defaults to the method of the workspace")
+                                .value_parser(["weighted-sum", "weighted-product", "min-max", "z-score", "topsis"])
+                        )
+                        .after_help(concat!(
+                            "Uncertain weights like 1..4 or 1..2..5 are drawn from their own range,\n",
+                            "exact weights are varied by the weight variation within the scale of the criterion."
+                        ))
+                )
+                .subcommand(
+                    Command::new("sensitivity")
+                        .about("Show how far each priority can change before the top-ranked project changes")
+                        .alias("s")
+                        .after_help(concat!(
+                            "Each priority of the active priority set is changed on its own, based on the\n",
+                            "weighted sum of the expected weights."
+                        ))
+                )
+        )
+}
+
+/// Arguments describing the optional details of a criterion.
+fn criterion_details() -> Vec<Arg> {
+    vec![
+        arg!(--cost "Lower weights are better").conflicts_with("benefit"),
+        arg!(--benefit "Higher weights are better"),
+        arg!(--min <MIN> "Lowest valid weight").value_parser(value_parser!(f64)),
+        arg!(--max <MAX> "Highest valid weight").value_parser(value_parser!(f64)),
+        arg!(--unit <UNIT> "Unit of the weights"),
+        arg!(--description <DESCRIPTION> "Description of the criterion"),
+        arg!(--"budget-cost" "Use the weights as the costs of the projects when selecting portfolios"),
+    ]
 }
@@ -1,45 +1,74 @@
 use std::error::Error;
 use std::fmt::{self, Display, Formatter};
-use std::fs::{self, remove_file, File};
-use std::io::{self, Read, Write};
+use std::fs::{self, File};
+use std::io::{self, Read};
 use std::path::PathBuf;
+use std::time::Duration;
 
 use anyhow::{Context, Result};
 use dirs::config_dir;
 use mockall::automock;
+use recision::storage;
 use serde::{Deserialize, Serialize};
 
 use crate::test_utils;
 
-#[derive(Debug, Default, Serialize, Deserialize)]
+#[derive(Debug, Serialize, Deserialize)]
 pub struct Config {
     active_workspace: Option<PathBuf>,
     active_priority_set: Option<usize>,
+    /// Whether the previous version of the workspace file is kept as a backup when writing it,
+    /// which is off unless enabled in the configuration file.
+    #[serde(default = "default_backup")]
+    backup: bool,
+    /// Seconds to wait for another process to release the lock on the workspace file.
+    #[serde(default = "default_lock_timeout")]
+    lock_timeout: f64,
 }
 
-impl Config {
-    fn write_to_file(&self, path: PathBuf) -> Result<()> {
-        if path.exists() {
-            remove_file(path.clone())?;
-        }
+fn default_backup() -> bool {
+    false
+}
 
-        let toml_string = toml::to_string_pretty(self)?;
-        let mut file = File::create(path)?;
-        file.write_all(toml_string.as_bytes())?;
+fn default_lock_timeout() -> f64 {
+    10.0
+}
 
-        Ok(())
+impl Default for Config {
+    fn default() -> Self {
+        Self {
+            active_workspace: None,
+            active_priority_set: None,
+            backup: default_backup(),
+            lock_timeout: default_lock_timeout(),
+        }
+    }
+}
+
+impl Config {
+    fn write_to_file(&self, path: PathBuf) -> Result<()> {
+        storage::write_toml(self, &path, false)
     }
 
     fn read_from_file(path: PathBuf) -> Result<Self> {
         let mut file = File::open(path.clone())?;
         let mut toml_string = String::new();
         file.read_to_string(&mut toml_string)?;
-        let config = toml::from_str(toml_string.as_str()).with_context(|| {
+        let config: Config = toml::from_str(toml_string.as_str()).with_context(|| {
             format!(
                 "Unable to parse the contents of the configuration file '{}'",
                 path.to_str().unwrap()
             )
         })?;
+        if Duration::try_from_secs_f64(config.lock_timeout).is_err() {
+            return Err(ConfigError {
+                message: format!(
+                    "lock_timeout {} is not a valid number of seconds",
+                    config.lock_timeout
+                ),
+            }
+            .into());
+        }
 
         Ok(config)
     }
@@ -48,6 +77,15 @@ impl Config {
         &self.active_workspace
     }
 
+    pub fn keeps_backup(&self) -> bool {
+        self.backup
+    }
+
+    pub fn get_lock_timeout(&self) -> Duration {
+        Duration::try_from_secs_f64(self.lock_timeout)
+            .expect("lock timeout is checked when the configuration is read")
+    }
+
     pub fn set_workspace(&mut self, path: Option<PathBuf>) -> Result<()> {
         if path.as_ref().is_some_and(|path| !path.exists()) {
             return Err(io::Error::new(
@@ -179,4 +217,18 @@ mod tests {
         let result = get_configuration(&provider);
         assert!(result.is_ok());
     }
+
+    #[test]
+    fn test_read_lock_timeout() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("config.toml");
+        for timeout in ["-1.0", "nan", "inf"] {
+            fs::write(&path, format!("lock_timeout = {timeout}\n")).unwrap();
+            assert!(Config::read_from_file(path.clone()).is_err());
+        }
+
+        fs::write(&path, "lock_timeout = 2.5\n").unwrap();
+        let config = Config::read_from_file(path).unwrap();
+        assert_eq!(Duration::from_millis(2500), config.get_lock_timeout());
+    }
 }
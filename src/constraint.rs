@@ -0,0 +1,125 @@
+//! Hard constraints that veto projects regardless of their score.
+
+use std::fmt::{self, Display, Formatter};
+
+use anyhow::Result;
+use serde::{Deserialize, Serialize};
+
+use crate::{RecicionError, Weight};
+
+/// Bounds that the weights of a criterion must stay within.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct Constraint {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    min: Option<f64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    max: Option<f64>,
+}
+
+impl Constraint {
+    pub fn new(min: Option<f64>, max: Option<f64>) -> Result<Self> {
+        if min.is_none() && max.is_none() {
+            return Err(
+                RecicionError::new("constraint needs a minimum or a maximum".into()).into(),
+            );
+        }
+        if let (Some(min), Some(max)) = (min, max) {
+            if min > max {
+                return Err(RecicionError::new(format!(
+                    "minimum {} of constraint is above its maximum {}",
+                    min, max
+                ))
+                .into());
+            }
+        }
+
+        Ok(Self { min, max })
+    }
+
+    pub fn get_min(&self) -> Option<f64> {
+        self.min
+    }
+
+    pub fn get_max(&self) -> Option<f64> {
+        self.max
+    }
+
+    /// Describes how a weight violates the constraint, judged by its expected value.
+    pub fn violation(&self, weight: &Weight) -> Option<String> {
+        let value = weight.expected();
+        match (self.min, self.max) {
+            (Some(min), _) if value < min => {
+                Some(format!("{weight} is below the minimum of {min}"))
+            }
+            (_, Some(max)) if value > max => {
+                Some(format!("{weight} is above the maximum of {max}"))
+            }
+            _ => None,
+        }
+    }
+}
+
+impl Display for Constraint {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        match (self.min, self.max) {
+            (Some(min), Some(max)) => write!(f, "between {min} and {max}"),
+            (Some(min), None) => write!(f, "≥ {min}"),
+            (None, Some(max)) => write!(f, "≤ {max}"),
+            (None, None) => write!(f, "unconstrained"),
+        }
+    }
+}
+
+/// A project that violates a constraint of the active priority set.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Veto {
+    pub project: String,
+    pub criterion: String,
+    /// Reason for the veto, e.g. `4 is above the maximum of 3`.
+    pub reason: String,
+}
+
+impl Display for Veto {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        write!(f, "{}: {}", self.criterion, self.reason)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_new() {
+        assert!(Constraint::new(None, None).is_err());
+        assert!(Constraint::new(Some(3.0), Some(1.0)).is_err());
+        assert!(Constraint::new(Some(1.0), Some(1.0)).is_ok());
+    }
+
+    #[test]
+    fn test_violation() {
+        let constraint = Constraint::new(Some(1.0), Some(3.0)).unwrap();
+
+        assert_eq!(None, constraint.violation(&Weight::Exact(3.0)));
+        assert_eq!(
+            Some("4 is above the maximum of 3".to_string()),
+            constraint.violation(&Weight::Exact(4.0))
+        );
+        assert_eq!(
+            Some("0.5 is below the minimum of 1".to_string()),
+            constraint.violation(&Weight::Exact(0.5))
+        );
+        assert_eq!(
+            Some("2..8 is above the maximum of 3".to_string()),
+            constraint.violation(&Weight::range(2.0, 8.0).unwrap())
+        );
+    }
+
+    #[test]
+    fn test_display() {
+        let constraint = |min, max| Constraint::new(min, max).unwrap().to_string();
+        assert_eq!("≤ 3", constraint(None, Some(3.0)));
+        assert_eq!("≥ 1.5", constraint(Some(1.5), None));
+        assert_eq!("between 1 and 3", constraint(Some(1.0), Some(3.0)));
+    }
+}
@@ -0,0 +1,202 @@
+//! Rankings that take the dependencies between projects into account.
+
+use std::collections::HashMap;
+
+use anyhow::Result;
+
+use crate::{Project, RecicionError, Status, Workspace};
+
+/// Share of the scores of its dependents that a prerequisite inherits by default.
+pub const DEFAULT_SHARE: f64 = 0.5;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum DependencyMode {
+    /// Projects never rank above their prerequisites.
+    Prerequisites,
+    /// Projects inherit a share of the adjusted scores of the projects that depend on them.
+    Inherit { share: f64 },
+}
+
+/// Ranks the projects by their scores, adjusted for their dependencies.
+///
+/// Returns the projects from the first to the last rank together with their adjusted score,
+/// which is the plain score unless projects inherit scores from their dependents. Prerequisites
+/// that are done no longer hold back or inherit from their dependents.
+pub fn rank(
+    workspace: &Workspace,
+    scores: &HashMap<String, f64>,
+    mode: DependencyMode,
+) -> Result<Vec<(String, f64)>> {
+    if let Some(cycle) = workspace.find_dependency_cycle() {
+        return Err(RecicionError::new(format!(
+            "dependencies form the cycle {}",
+            cycle.join(" -> ")
+        ))
+        .into());
+    }
+
+    let order = prerequisites_first(workspace, scores);
+    match mode {
+        DependencyMode::Prerequisites => Ok(order
+            .into_iter()
+            .map(|name| {
+                let score = scores[&name];
+                (name, score)
+            })
+            .collect()),
+        DependencyMode::Inherit { share } => {
+            // dependents come after their prerequisites, so their adjusted scores are known first
+            let mut adjusted: HashMap<String, f64> = HashMap::new();
+            for name in order.iter().rev() {
+                let inherited: f64 = workspace
+                    .projects
+                    .iter()
+                    .filter(|project| pending_prerequisites(workspace, project).any(|p| p == name))
+                    .map(|dependent| adjusted[&dependent.name])
+                    .sum();
+                adjusted.insert(name.clone(), scores[name] + share * inherited);
+            }
+
+            let mut ranking: Vec<_> = workspace
+                .get_project_names()
+                .into_iter()
+                .map(|name| {
+                    let score = adjusted[&name];
+                    (name, score)
+                })
+                .collect();
+            ranking.sort_by(|(_, a), (_, b)| b.total_cmp(a));
+            Ok(ranking)
+        }
+    }
+}
+
+/// Orders the projects by decreasing score, except that every project comes after all of its
+/// prerequisites.
+fn prerequisites_first(workspace: &Workspace, scores: &HashMap<String, f64>) -> Vec<String> {
+    let mut remaining: Vec<_> = workspace.projects.iter().collect();
+    let mut order: Vec<String> = Vec::with_capacity(remaining.len());
+
+    while !remaining.is_empty() {
+        let next = remaining
+            .iter()
+            .enumerate()
+            .filter(|(_, project)| {
+                pending_prerequisites(workspace, project)
+                    .all(|dependency| order.contains(dependency))
+            })
+            // the first of equal scores keeps the order of the workspace
+            .reduce(|best, candidate| {
+                if scores[&candidate.1.name] > scores[&best.1.name] {
+                    candidate
+                } else {
+                    best
+                }
+            })
+            .map(|(i, _)| i)
+            .expect("acyclic dependencies leave a project without remaining prerequisites");
+        order.push(remaining.remove(next).name.clone());
+    }
+
+    order
+}
+
+/// Returns the prerequisites of a project that aren't done yet.
+fn pending_prerequisites<'a>(
+    workspace: &'a Workspace,
+    project: &'a Project,
+) -> impl Iterator<Item = &'a String> {
+    project.dependencies.iter().filter(|dependency| {
+        workspace
+            .projects
+            .iter()
+            .any(|p| &p.name == *dependency && p.status != Status::Done)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Criterion, Project};
+
+    fn build_test_workspace() -> Workspace {
+        let mut workspace = Workspace::new();
+        for name in ["Foundation", "House", "Garden", "Roof"] {
+            workspace.add_project(Project::new(name)).unwrap();
+        }
+        workspace.add_criterion(Criterion::new("Fun")).unwrap();
+        workspace.add_priority_set("Workday").unwrap();
+        workspace.activate_priority_set("Workday").unwrap();
+
+        workspace.set_weight("Foundation", "Fun", 1).unwrap();
+        workspace.set_weight("House", "Fun", 8).unwrap();
+        workspace.set_weight("Garden", "Fun", 4).unwrap();
+        workspace.set_weight("Roof", "Fun", 2).unwrap();
+        workspace.add_dependency("House", "Foundation").unwrap();
+        workspace.add_dependency("Roof", "House").unwrap();
+
+        workspace
+    }
+
+    fn names(ranking: &[(String, f64)]) -> Vec<&str> {
+        ranking.iter().map(|(name, _)| name.as_str()).collect()
+    }
+
+    #[test]
+    fn test_rank_prerequisites_first() {
+        let ws = build_test_workspace();
+        let scores = ws.calculate_score().unwrap();
+
+        let ranking = rank(&ws, &scores, DependencyMode::Prerequisites).unwrap();
+
+        assert_eq!(
+            vec!["Garden", "Foundation", "House", "Roof"],
+            names(&ranking)
+        );
+        assert_eq!(1.0, ranking[1].1);
+    }
+
+    #[test]
+    fn test_rank_inherit() {
+        let ws = build_test_workspace();
+        let scores = ws.calculate_score().unwrap();
+
+        let ranking = rank(&ws, &scores, DependencyMode::Inherit { share: 0.5 }).unwrap();
+
+        assert_eq!(
+            vec!["House", "Foundation", "Garden", "Roof"],
+            names(&ranking)
+        );
+        assert_eq!(9.0, ranking[0].1);
+        assert_eq!(5.5, ranking[1].1);
+    }
+
+    #[test]
+    fn test_rank_ignores_done_prerequisites() {
+        let mut ws = build_test_workspace();
+        ws.set_status("Foundation", Status::Done).unwrap();
+        let scores = ws.calculate_score().unwrap();
+
+        let ranking = rank(&ws, &scores, DependencyMode::Prerequisites).unwrap();
+        assert_eq!(
+            vec!["House", "Garden", "Roof", "Foundation"],
+            names(&ranking)
+        );
+
+        let ranking = rank(&ws, &scores, DependencyMode::Inherit { share: 0.5 }).unwrap();
+        assert_eq!(1.0, ranking[3].1);
+    }
+
+    #[test]
+    fn test_rank_with_cycle() {
+        let mut ws = build_test_workspace();
+        ws.projects[0].dependencies.push("Roof".into());
+        let scores = ws.calculate_score().unwrap();
+
+        let error = rank(&ws, &scores, DependencyMode::Prerequisites).unwrap_err();
+        assert_eq!(
+            "dependencies form the cycle Foundation -> Roof -> House -> Foundation",
+            error.to_string()
+        );
+    }
+}
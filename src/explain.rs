@@ -0,0 +1,239 @@
+//! Breakdown of scores into the contributions of the criteria.
+
+use anyhow::Result;
+
+use crate::{scoring::Method, RecicionError, Scenario, Weight, Workspace};
+
+/// Part of a score that comes from a single criterion.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Contribution {
+    pub criterion: String,
+    pub weight: Weight,
+    pub priority: f64,
+    /// Product of the priority and the expected weight, oriented by the direction of the
+    /// criterion and normalized like the scoring method does.
+    pub contribution: f64,
+    /// Fraction of the score that the contribution accounts for, 0 if the score is 0.
+    pub share: f64,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Explanation {
+    pub project: String,
+    /// Scoring method the score is calculated with.
+    pub method: Method,
+    pub score: f64,
+    /// Contributions in the order of the criteria.
+    pub contributions: Vec<Contribution>,
+}
+
+/// Difference of the contributions of one criterion to the scores of two projects.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Difference {
+    pub criterion: String,
+    pub first: f64,
+    pub second: f64,
+    /// How far the first project is ahead of the second one on the criterion.
+    pub difference: f64,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct ProjectComparison {
+    pub first: Explanation,
+    pub second: Explanation,
+    /// Differences ordered from the criterion separating the projects the most to the least.
+    pub differences: Vec<Difference>,
+}
+
+/// Explains the score of a project under the active priority set with the scoring method of the
+/// workspace.
+///
+/// Only the scores of methods that sum up over the criteria can be broken down, which rules out
+/// the weighted product and TOPSIS.
+pub fn explain(workspace: &Workspace, project: &str) -> Result<Explanation> {
+    let index = workspace
+        .projects
+        .iter()
+        .position(|p| p.name == project)
+        .ok_or(RecicionError::new(format!("no project {}", project)))?;
+    let method = workspace.get_scoring_method();
+    let (weights, priorities) = workspace.scoring_input(Scenario::Expected)?;
+    let grid = workspace.weight_grid();
+
+    let contributions = method
+        .scoring_method()
+        .contributions(&weights, &priorities)
+        .ok_or(RecicionError::new(format!(
+            "scores of the {} method can't be broken down by criterion",
+            method
+        )))?
+        .swap_remove(index);
+    let score: f64 = contributions.iter().sum();
+
+    let contributions = workspace
+        .criteria
+        .iter()
+        .zip(&grid[index])
+        .zip(priorities)
+        .zip(contributions)
+        .map(
+            |(((criterion, weight), priority), contribution)| Contribution {
+                criterion: criterion.name.clone(),
+                weight: *weight,
+                priority,
+                contribution,
+                share: if score == 0.0 {
+                    0.0
+                } else {
+                    contribution / score
+                },
+            },
+        )
+        .collect();
+
+    Ok(Explanation {
+        project: project.into(),
+        method,
+        score,
+        contributions,
+    })
+}
+
+/// Compares the scores of two projects criterion by criterion, see [`explain`].
+pub fn compare(workspace: &Workspace, first: &str, second: &str) -> Result<ProjectComparison> {
+    let first = explain(workspace, first)?;
+    let second = explain(workspace, second)?;
+
+    let mut differences: Vec<_> = first
+        .contributions
+        .iter()
+        .zip(&second.contributions)
+        .map(|(a, b)| Difference {
+            criterion: a.criterion.clone(),
+            first: a.contribution,
+            second: b.contribution,
+            difference: a.contribution - b.contribution,
+        })
+        .collect();
+    differences.sort_by(|a, b| b.difference.abs().total_cmp(&a.difference.abs()));
+
+    Ok(ProjectComparison {
+        first,
+        second,
+        differences,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Criterion, Direction, Project};
+
+    fn build_test_workspace() -> Workspace {
+        let mut workspace = Workspace::new();
+        workspace
+            .add_project(Project::new("Project 1"))
+            .unwrap()
+            .add_project(Project::new("Project 2"))
+            .unwrap();
+        workspace
+            .add_criterion(Criterion::new("Fun"))
+            .unwrap()
+            .add_criterion(Criterion::new("Useful"))
+            .unwrap()
+            .add_criterion(Criterion::new("Effort"))
+            .unwrap();
+        workspace
+            .get_criterion("Effort")
+            .unwrap()
+            .set_direction(Direction::Cost);
+        workspace.add_priority_set("Workday").unwrap();
+        workspace.activate_priority_set("Workday").unwrap();
+        workspace.set_priority("Useful", 2.0).unwrap();
+
+        workspace.set_weight("Project 1", "Fun", 3).unwrap();
+        workspace.set_weight("Project 1", "Useful", 2).unwrap();
+        workspace.set_weight("Project 1", "Effort", 1).unwrap();
+        workspace
+            .set_weight("Project 2", "Fun", Weight::range(1.0, 3.0).unwrap())
+            .unwrap();
+        workspace.set_weight("Project 2", "Useful", 4).unwrap();
+
+        workspace
+    }
+
+    #[test]
+    fn test_explain() {
+        let ws = build_test_workspace();
+        let explanation = explain(&ws, "Project 1").unwrap();
+
+        assert_eq!(6.0, explanation.score);
+        assert_eq!(
+            ws.calculate_score().unwrap()["Project 1"],
+            explanation.score
+        );
+
+        let contributions: Vec<_> = explanation
+            .contributions
+            .iter()
+            .map(|c| (c.criterion.as_str(), c.contribution, c.share))
+            .collect();
+        assert_eq!(
+            vec![
+                ("Fun", 3.0, 0.5),
+                ("Useful", 4.0, 4.0 / 6.0),
+                ("Effort", -1.0, -1.0 / 6.0),
+            ],
+            contributions
+        );
+        assert_eq!(Weight::Exact(2.0), explanation.contributions[1].weight);
+        assert_eq!(2.0, explanation.contributions[1].priority);
+    }
+
+    #[test]
+    fn test_explain_with_scoring_method() {
+        let mut ws = build_test_workspace();
+        ws.set_scoring_method(Method::MinMax);
+        let explanation = explain(&ws, "Project 2").unwrap();
+
+        assert_eq!(
+            ws.calculate_score().unwrap()["Project 2"],
+            explanation.score
+        );
+        let contributions: Vec<_> = explanation
+            .contributions
+            .iter()
+            .map(|c| c.contribution)
+            .collect();
+        assert_eq!(vec![0.0, 2.0, 0.0], contributions);
+
+        ws.set_scoring_method(Method::Topsis);
+        assert!(explain(&ws, "Project 2").is_err());
+    }
+
+    #[test]
+    fn test_explain_unknown_project() {
+        let ws = build_test_workspace();
+        assert!(explain(&ws, "Project 3").is_err());
+    }
+
+    #[test]
+    fn test_compare() {
+        let ws = build_test_workspace();
+        let comparison = compare(&ws, "Project 1", "Project 2").unwrap();
+
+        assert_eq!(6.0, comparison.first.score);
+        // the unrated effort of Project 2 counts as the highest effort of any project
+        assert_eq!(9.0, comparison.second.score);
+
+        let differences: Vec<_> = comparison
+            .differences
+            .iter()
+            .map(|d| (d.criterion.as_str(), d.difference))
+            .collect();
+        assert_eq!(
+            vec![("Useful", -4.0), ("Fun", 1.0), ("Effort", 0.0)],
+            differences
+        );
+    }
+}
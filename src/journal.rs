@@ -0,0 +1,449 @@
+//! Append-only journal of the changes to a workspace file, which makes them reversible.
+//!
+//! Every change records the operation that made it and the part of the workspace file it
+//! replaced, along with checksums of the file before and after it. Undoing and redoing a change
+//! are recorded as changes of their own, so the journal is never rewritten.
+
+use std::{
+    fmt::{self, Display, Formatter},
+    fs::{self, OpenOptions},
+    io::{Read, Seek, SeekFrom, Write},
+    path::{Path, PathBuf},
+    str,
+};
+
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use toml::value::Datetime;
+
+use crate::{status, storage, RecicionError};
+
+/// Header that every entry starts with in the journal file.
+const ENTRY_HEADER: &str = "[[entries]]";
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum Action {
+    Change,
+    Undo,
+    Redo,
+}
+
+/// Subcommand that changed the workspace file, e.g. `weight set`, and its arguments.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Operation {
+    pub kind: String,
+    pub args: Vec<String>,
+}
+
+impl Display for Operation {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.kind)?;
+        for arg in &self.args {
+            if arg.is_empty() || arg.contains(char::is_whitespace) {
+                write!(f, " '{arg}'")?;
+            } else {
+                write!(f, " {arg}")?;
+            }
+        }
+        Ok(())
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Entry {
+    pub at: Datetime,
+    pub action: Action,
+    /// Operation of the change, also when the change is undone or redone.
+    pub operation: Operation,
+    diff: Diff,
+}
+
+/// Replacement of a single stretch of text, which turns the contents before a change into the
+/// contents after it and back.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+struct Diff {
+    /// Byte offset of the replaced text.
+    offset: usize,
+    removed: String,
+    added: String,
+    before_checksum: String,
+    after_checksum: String,
+}
+
+impl Diff {
+    fn new(before: &str, after: &str) -> Self {
+        let mut prefix = before
+            .bytes()
+            .zip(after.bytes())
+            .take_while(|(a, b)| a == b)
+            .count();
+        while !before.is_char_boundary(prefix) || !after.is_char_boundary(prefix) {
+            prefix -= 1;
+        }
+        let mut suffix = before[prefix..]
+            .bytes()
+            .rev()
+            .zip(after[prefix..].bytes().rev())
+            .take_while(|(a, b)| a == b)
+            .count();
+        while !before.is_char_boundary(before.len() - suffix)
+            || !after.is_char_boundary(after.len() - suffix)
+        {
+            suffix -= 1;
+        }
+
+        Self {
+            offset: prefix,
+            removed: before[prefix..before.len() - suffix].into(),
+            added: after[prefix..after.len() - suffix].into(),
+            before_checksum: checksum(before),
+            after_checksum: checksum(after),
+        }
+    }
+
+    /// Returns the contents before the change if `current` is the contents after it, or the
+    /// contents after the change if `current` is the contents before it, depending on `revert`.
+    fn apply(&self, current: &str, revert: bool) -> Option<String> {
+        let (expected, removed, added) = if revert {
+            (&self.after_checksum, &self.added, &self.removed)
+        } else {
+            (&self.before_checksum, &self.removed, &self.added)
+        };
+        if &checksum(current) != expected {
+            return None;
+        }
+
+        let end = self.offset + removed.len();
+        Some(format!(
+            "{}{}{}",
+            current.get(..self.offset)?,
+            added,
+            current.get(end..)?
+        ))
+    }
+}
+
+/// Returns the 64 bit FNV-1a hash of the contents, which is stable across platforms and
+/// versions, as a hexadecimal string.
+fn checksum(contents: &str) -> String {
+    let hash = contents
+        .bytes()
+        .fold(0xcbf2_9ce4_8422_2325_u64, |hash, byte| {
+            (hash ^ u64::from(byte)).wrapping_mul(0x0100_0000_01b3)
+        });
+    format!("{hash:016x}")
+}
+
+#[derive(Serialize, Deserialize)]
+struct Document {
+    #[serde(default)]
+    entries: Vec<Entry>,
+}
+
+/// Returns the path of the journal of a workspace file.
+pub fn journal_path(workspace_path: &Path) -> PathBuf {
+    storage::sibling_path(workspace_path, ".journal")
+}
+
+/// Records a change of a workspace file by appending it to the journal.
+///
+/// Nothing is recorded if the contents didn't change.
+pub fn record(
+    workspace_path: &Path,
+    operation: Operation,
+    before: &str,
+    after: &str,
+) -> Result<()> {
+    if before == after {
+        return Ok(());
+    }
+
+    append(
+        workspace_path,
+        Entry {
+            at: status::now(),
+            action: Action::Change,
+            operation,
+            diff: Diff::new(before, after),
+        },
+    )
+}
+
+/// Appends an entry to the journal, dropping an entry that was cut off before.
+fn append(workspace_path: &Path, entry: Entry) -> Result<()> {
+    let path = journal_path(workspace_path);
+    let toml_string = toml::to_string_pretty(&Document {
+        entries: vec![entry],
+    })?;
+
+    let mut file = OpenOptions::new()
+        .read(true)
+        .write(true)
+        .create(true)
+        .truncate(false)
+        .open(&path)
+        .with_context(|| format!("opening journal {}", path.display()))?;
+    let mut contents = Vec::new();
+    file.read_to_end(&mut contents)
+        .with_context(|| format!("reading journal {}", path.display()))?;
+    let (_, length) = parse(&contents)
+        .with_context(|| format!("parsing contents of journal {}", path.display()))?;
+
+    file.set_len(length as u64)?;
+    file.seek(SeekFrom::Start(length as u64))?;
+    file.write_all(toml_string.as_bytes())?;
+    file.sync_all()?;
+
+    Ok(())
+}
+
+/// Parses the entries of a journal and returns them with the number of bytes they take up.
+///
+/// A crash while an entry is appended can leave it cut off at the end of the journal, which is
+/// left out rather than making the whole journal unreadable.
+fn parse(contents: &[u8]) -> Result<(Vec<Entry>, usize)> {
+    let contents = match str::from_utf8(contents) {
+        Ok(contents) => contents,
+        // cut off in the middle of a character
+        Err(error) if error.error_len().is_none() => {
+            str::from_utf8(&contents[..error.valid_up_to()]).expect("prefix is valid UTF-8")
+        }
+        Err(error) => return Err(error.into()),
+    };
+
+    match toml::from_str::<Document>(contents) {
+        Ok(document) => Ok((document.entries, contents.len())),
+        Err(error) => {
+            let last_entry = contents
+                .match_indices(ENTRY_HEADER)
+                .map(|(index, _)| index)
+                .filter(|&index| index == 0 || contents[..index].ends_with('\n'))
+                .last()
+                .ok_or(error)?;
+            let document: Document = toml::from_str(&contents[..last_entry])?;
+            Ok((document.entries, last_entry))
+        }
+    }
+}
+
+pub struct Journal {
+    workspace_path: PathBuf,
+    entries: Vec<Entry>,
+}
+
+impl Journal {
+    /// Reads the journal of a workspace file, which is empty if no change was recorded yet.
+    pub fn read(workspace_path: &Path) -> Result<Self> {
+        let path = journal_path(workspace_path);
+        let entries = if path.exists() {
+            let contents =
+                fs::read(&path).with_context(|| format!("reading journal {}", path.display()))?;
+            parse(&contents)
+                .with_context(|| format!("parsing contents of journal {}", path.display()))?
+                .0
+        } else {
+            Vec::new()
+        };
+
+        Ok(Self {
+            workspace_path: workspace_path.into(),
+            entries,
+        })
+    }
+
+    pub fn get_entries(&self) -> &[Entry] {
+        &self.entries
+    }
+
+    /// Returns whether the change at the index is currently undone.
+    pub fn is_undone(&self, index: usize) -> bool {
+        self.replay().1.contains(&index)
+    }
+
+    /// Reverts the workspace file to its contents before the latest change that isn't undone.
+    ///
+    /// Returns the change that was undone.
+    pub fn undo(&mut self, backup: bool) -> Result<&Entry> {
+        let (done, _) = self.replay();
+        let index = *done
+            .last()
+            .ok_or(RecicionError::new("nothing to undo".into()))?;
+        self.apply(index, Action::Undo, backup)
+    }
+
+    /// Applies the latest undone change to the workspace file again.
+    ///
+    /// Returns the change that was redone.
+    pub fn redo(&mut self, backup: bool) -> Result<&Entry> {
+        let (_, undone) = self.replay();
+        let index = *undone
+            .last()
+            .ok_or(RecicionError::new("nothing to redo".into()))?;
+        self.apply(index, Action::Redo, backup)
+    }
+
+    fn apply(&mut self, index: usize, action: Action, backup: bool) -> Result<&Entry> {
+        let change = &self.entries[index];
+        let current = fs::read_to_string(&self.workspace_path)
+            .with_context(|| "reading workspace from file")?;
+        let target =
+            change
+                .diff
+                .apply(&current, action == Action::Undo)
+                .ok_or(RecicionError::new(
+                    "workspace file was changed outside of recision since the change was recorded"
+                        .into(),
+                ))?;
+        storage::write_atomically(&self.workspace_path, target.as_bytes(), backup)?;
+
+        let entry = Entry {
+            at: status::now(),
+            action,
+            operation: change.operation.clone(),
+            diff: Diff::new(&current, &target),
+        };
+        append(&self.workspace_path, entry.clone())?;
+        self.entries.push(entry);
+
+        Ok(&self.entries[index])
+    }
+
+    /// Replays the journal and returns the indices of the changes that are in effect and of the
+    /// changes that are undone, with the latest last.
+    fn replay(&self) -> (Vec<usize>, Vec<usize>) {
+        let mut done = Vec::new();
+        let mut undone = Vec::new();
+        for (index, entry) in self.entries.iter().enumerate() {
+            match entry.action {
+                Action::Change => {
+                    done.push(index);
+                    undone.clear();
+                }
+                Action::Undo => undone.extend(done.pop()),
+                Action::Redo => done.extend(undone.pop()),
+            }
+        }
+
+        (done, undone)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+
+    /// Changes the workspace file and records the change like the command line does.
+    fn change(path: &Path, kind: &str, contents: &str) {
+        let before = fs::read_to_string(path).unwrap();
+        fs::write(path, contents).unwrap();
+        let operation = Operation {
+            kind: kind.into(),
+            args: Vec::new(),
+        };
+        record(path, operation, &before, contents).unwrap();
+    }
+
+    #[test]
+    fn test_undo_and_redo() {
+        let dir = tempdir().unwrap();
+        let path = dir.path().join("workspace.toml");
+        fs::write(&path, "0").unwrap();
+        change(&path, "first", "1");
+        change(&path, "second", "2");
+
+        let mut journal = Journal::read(&path).unwrap();
+        assert_eq!("second", journal.undo(false).unwrap().operation.kind);
+        assert_eq!("1", fs::read_to_string(&path).unwrap());
+        assert_eq!("first", journal.undo(false).unwrap().operation.kind);
+        assert_eq!("0", fs::read_to_string(&path).unwrap());
+        assert!(journal.undo(false).is_err());
+
+        assert_eq!("first", journal.redo(false).unwrap().operation.kind);
+        assert_eq!("1", fs::read_to_string(&path).unwrap());
+
+        let journal = Journal::read(&path).unwrap();
+        assert_eq!(5, journal.get_entries().len());
+        assert!(!journal.is_undone(0));
+        assert!(journal.is_undone(1));
+    }
+
+    #[test]
+    fn test_change_after_undo_discards_redo() {
+        let dir = tempdir().unwrap();
+        let path = dir.path().join("workspace.toml");
+        fs::write(&path, "0").unwrap();
+        change(&path, "first", "1");
+        Journal::read(&path).unwrap().undo(false).unwrap();
+        change(&path, "second", "2");
+        change(&path, "unchanged", "2");
+
+        let mut journal = Journal::read(&path).unwrap();
+        assert_eq!(3, journal.get_entries().len());
+        assert!(journal.redo(false).is_err());
+    }
+
+    #[test]
+    fn test_undo_after_outside_change() {
+        let dir = tempdir().unwrap();
+        let path = dir.path().join("workspace.toml");
+        fs::write(&path, "0").unwrap();
+        change(&path, "first", "1");
+        fs::write(&path, "edited").unwrap();
+
+        assert!(Journal::read(&path).unwrap().undo(false).is_err());
+        assert_eq!("edited", fs::read_to_string(&path).unwrap());
+    }
+
+    #[test]
+    fn test_diff() {
+        let diff = Diff::new("name = \"Fünf\"\n", "name = \"Füne\"\n");
+        assert_eq!("f", diff.removed);
+        assert_eq!("e", diff.added);
+        assert_eq!(
+            Some("name = \"Füne\"\n".to_string()),
+            diff.apply("name = \"Fünf\"\n", false)
+        );
+        assert_eq!(
+            Some("name = \"Fünf\"\n".to_string()),
+            diff.apply("name = \"Füne\"\n", true)
+        );
+        assert_eq!(None, diff.apply("name = \"Füne\"\n", false));
+
+        let diff = Diff::new("ä", "ö");
+        assert_eq!("ä", diff.removed);
+        assert_eq!(Some("ö".to_string()), diff.apply("ä", false));
+    }
+
+    #[test]
+    fn test_cut_off_entry() {
+        let dir = tempdir().unwrap();
+        let path = dir.path().join("workspace.toml");
+        fs::write(&path, "0").unwrap();
+        change(&path, "first", "1");
+        change(&path, "second", "2");
+
+        let journal_path = journal_path(&path);
+        let complete = fs::read(&journal_path).unwrap();
+        let mut cut_off = complete.clone();
+        cut_off
+            .extend_from_slice(b"\n[[entries]]\nat = 2026-10-18T07:00:00Z\naction = \"change\"\n");
+        // cut off in the middle of the two bytes of a character
+        cut_off.extend_from_slice(b"\n[entries.operation]\nkind = \"\xc3");
+        fs::write(&journal_path, &cut_off).unwrap();
+
+        let mut journal = Journal::read(&path).unwrap();
+        assert_eq!(2, journal.get_entries().len());
+        assert_eq!("second", journal.undo(false).unwrap().operation.kind);
+        assert_eq!("1", fs::read_to_string(&path).unwrap());
+
+        // the cut-off entry is dropped before the undo is appended
+        let contents = fs::read_to_string(&journal_path).unwrap();
+        assert_eq!(
+            3,
+            toml::from_str::<Document>(&contents).unwrap().entries.len()
+        );
+        assert!(contents.starts_with(str::from_utf8(&complete).unwrap()));
+    }
+}
@@ -1,14 +1,38 @@
 use core::fmt;
 use std::{
-    collections::HashMap,
+    collections::{HashMap, HashSet},
     fmt::{Display, Formatter},
-    fs::{remove_file, File},
-    io::{Read, Write},
+    fs::File,
+    io::Read,
     path::PathBuf,
 };
 
+use ahp::{AhpResult, Comparison};
 use anyhow::{Context, Ok, Result};
+use resolve::{resolve, ItemKind};
+use scoring::{Method, ScoreRange, ScoringMethod, Topsis, TopsisResult};
 use serde::{Deserialize, Serialize};
+use snapshot::Snapshot;
+use toml::value::Datetime;
+
+pub mod ahp;
+pub mod analysis;
+mod constraint;
+pub mod dependency;
+pub mod explain;
+pub mod journal;
+pub mod migration;
+pub mod portfolio;
+pub mod resolve;
+pub mod scoring;
+pub mod snapshot;
+mod status;
+pub mod storage;
+mod weight;
+
+pub use constraint::{Constraint, Veto};
+pub use status::{Status, StatusChange};
+pub use weight::Weight;
 
 #[derive(Debug)]
 pub struct RecicionError {
@@ -31,23 +55,40 @@ impl std::error::Error for RecicionError {}
 
 #[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
 pub struct Workspace {
+    /// Version of the file format, see [`migration`].
+    #[serde(default)]
+    schema_version: u32,
     projects: Vec<Project>,
     criteria: Vec<Criterion>,
     priority_sets: Vec<PrioritySet>,
     active_priority_set: Option<String>,
+    #[serde(default)]
+    scoring_method: Method,
+    /// Criterion whose weights are the costs of the projects when selecting portfolios.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    budget_criterion: Option<String>,
+    /// Recorded scores, from the oldest to the newest.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    snapshots: Vec<Snapshot>,
 }
 
 impl Workspace {
     pub fn new() -> Self {
         Self {
+            schema_version: migration::SCHEMA_VERSION,
             projects: Vec::new(),
             criteria: Vec::new(),
             priority_sets: Vec::new(),
             active_priority_set: None,
+            scoring_method: Method::default(),
+            budget_criterion: None,
+            snapshots: Vec::new(),
         }
     }
 
     pub fn add_project(&mut self, project: Project) -> Result<&mut Self> {
+        validate_name(project.get_name())?;
+
         if self.get_project(project.get_name()).is_some() {
             return Err(RecicionError::new(format!(
                 "project {} already exists",
@@ -60,10 +101,77 @@ impl Workspace {
         Ok(self)
     }
 
-    pub fn add_criterion(&mut self, criterion: Criterion) -> &mut Self {
-        // TODO check if criterion by the name already exists
+    pub fn add_criterion(&mut self, criterion: Criterion) -> Result<&mut Self> {
+        validate_name(criterion.get_name())?;
+
+        if self.get_criterion(criterion.get_name()).is_some() {
+            return Err(RecicionError::new(format!(
+                "criterion {} already exists",
+                criterion.get_name()
+            ))
+            .into());
+        }
+
         self.criteria.push(criterion);
-        self
+        Ok(self)
+    }
+
+    /// Removes a criterion together with all weights, priorities and constraints that refer to it.
+    pub fn remove_criterion(&mut self, name: &str) -> Result<()> {
+        let index = self
+            .criteria
+            .iter()
+            .position(|criterion| criterion.name == name)
+            .ok_or(RecicionError::new(format!("no criterion {}", name)))?;
+        self.criteria.remove(index);
+
+        self.projects.iter_mut().for_each(|project| {
+            project.weights.remove(name);
+        });
+        self.priority_sets.iter_mut().for_each(|ps| {
+            ps.priorities.remove(name);
+            ps.comparisons
+                .retain(|c| c.first != name && c.second != name);
+            ps.constraints.remove(name);
+        });
+        if self.budget_criterion.as_deref() == Some(name) {
+            self.budget_criterion = None;
+        }
+
+        Ok(())
+    }
+
+    /// Changes the scale of a criterion after checking that all existing weights fit into it.
+    pub fn set_criterion_scale(
+        &mut self,
+        name: &str,
+        min: Option<f64>,
+        max: Option<f64>,
+    ) -> Result<()> {
+        let mut criterion = self
+            .get_criterion(name)
+            .ok_or(RecicionError::new(format!("no criterion {}", name)))?
+            .clone();
+        criterion.set_scale(min, max)?;
+
+        for project in &self.projects {
+            if let Some(weight) = project.weights.get(name) {
+                criterion
+                    .validate_weight(weight)
+                    .with_context(|| format!("checking weight of project {}", project.name))?;
+            }
+        }
+
+        *self.get_criterion(name).expect("criterion exists") = criterion;
+        Ok(())
+    }
+
+    /// Moves the named criteria to the top of the order, keeping the relative order of the rest.
+    pub fn reorder_criteria(&mut self, names: &[String]) -> Result<()> {
+        self.criteria = reorder(&self.criteria, names, |criterion| criterion.get_name())
+            .with_context(|| "reordering criteria")?;
+
+        Ok(())
     }
 
     pub fn get_project(&mut self, name: &str) -> Option<&mut Project> {
@@ -72,17 +180,275 @@ impl Workspace {
             .find(|project| project.name == name)
     }
 
+    pub fn get_projects(&self) -> &[Project] {
+        &self.projects
+    }
+
     pub fn get_project_names(&self) -> Vec<String> {
         self.projects.iter().map(|p| p.name.clone()).collect()
     }
 
+    /// Removes a project together with its weights.
+    pub fn remove_project(&mut self, name: &str) -> Result<()> {
+        let index = self
+            .projects
+            .iter()
+            .position(|project| project.name == name)
+            .ok_or(RecicionError::new(format!("no project {}", name)))?;
+        self.projects.remove(index);
+
+        self.projects.iter_mut().for_each(|project| {
+            project.dependencies.retain(|dependency| dependency != name);
+        });
+
+        Ok(())
+    }
+
+    /// Declares that a project can't start before the prerequisite is done.
+    pub fn add_dependency(&mut self, project_name: &str, prerequisite_name: &str) -> Result<()> {
+        if self.get_project(prerequisite_name).is_none() {
+            return Err(RecicionError::new(format!("no project {}", prerequisite_name)).into());
+        }
+        if project_name == prerequisite_name {
+            return Err(RecicionError::new(format!(
+                "project {} can't depend on itself",
+                project_name
+            ))
+            .into());
+        }
+        if let Some(mut cycle) = self.dependency_path(prerequisite_name, project_name) {
+            cycle.insert(0, project_name.to_string());
+            return Err(RecicionError::new(format!(
+                "dependency would create the cycle {}",
+                cycle.join(" -> ")
+            ))
+            .into());
+        }
+
+        let project = self
+            .get_project(project_name)
+            .ok_or(RecicionError::new(format!("no project {}", project_name)))?;
+        if project.dependencies.iter().any(|d| d == prerequisite_name) {
+            return Err(RecicionError::new(format!(
+                "project {} already depends on {}",
+                project_name, prerequisite_name
+            ))
+            .into());
+        }
+        project.dependencies.push(prerequisite_name.into());
+
+        Ok(())
+    }
+
+    pub fn remove_dependency(&mut self, project_name: &str, prerequisite_name: &str) -> Result<()> {
+        let project = self
+            .get_project(project_name)
+            .ok_or(RecicionError::new(format!("no project {}", project_name)))?;
+        let index = project
+            .dependencies
+            .iter()
+            .position(|d| d == prerequisite_name)
+            .ok_or(RecicionError::new(format!(
+                "project {} doesn't depend on {}",
+                project_name, prerequisite_name
+            )))?;
+        project.dependencies.remove(index);
+
+        Ok(())
+    }
+
+    /// Returns the names of the projects that directly depend on the given project.
+    pub fn get_dependents(&self, name: &str) -> Vec<String> {
+        self.projects
+            .iter()
+            .filter(|project| project.dependencies.iter().any(|d| d == name))
+            .map(|project| project.name.clone())
+            .collect()
+    }
+
+    /// Finds a cycle among the dependencies of the projects, which can only exist if the
+    /// workspace file was edited by hand. The cycle starts and ends with the same project.
+    pub fn find_dependency_cycle(&self) -> Option<Vec<String>> {
+        self.projects.iter().find_map(|project| {
+            project.dependencies.iter().find_map(|dependency| {
+                self.dependency_path(dependency, &project.name)
+                    .map(|mut cycle| {
+                        cycle.insert(0, project.name.clone());
+                        cycle
+                    })
+            })
+        })
+    }
+
+    /// Finds a chain of dependencies that leads from one project to another, including both.
+    fn dependency_path(&self, from: &str, to: &str) -> Option<Vec<String>> {
+        let mut visited = HashSet::new();
+        let mut path = vec![from.to_string()];
+        self.extend_dependency_path(&mut path, to, &mut visited)
+            .then_some(path)
+    }
+
+    fn extend_dependency_path(
+        &self,
+        path: &mut Vec<String>,
+        to: &str,
+        visited: &mut HashSet<String>,
+    ) -> bool {
+        let current = path.last().expect("path starts with a project").clone();
+        if current == to {
+            return true;
+        }
+        if !visited.insert(current.clone()) {
+            return false;
+        }
+
+        let dependencies = self
+            .projects
+            .iter()
+            .find(|project| project.name == current)
+            .map(|project| project.dependencies.clone())
+            .unwrap_or_default();
+        for dependency in dependencies {
+            path.push(dependency);
+            if self.extend_dependency_path(path, to, visited) {
+                return true;
+            }
+            path.pop();
+        }
+
+        false
+    }
+
+    /// Changes the status of a project and records the change in its history.
+    pub fn set_status(&mut self, project_name: &str, status: Status) -> Result<()> {
+        let project = self
+            .get_project(project_name)
+            .ok_or(RecicionError::new(format!("no project {}", project_name)))?;
+        if project.status == status {
+            return Err(RecicionError::new(format!(
+                "project {} is already {}",
+                project_name, status
+            ))
+            .into());
+        }
+
+        project.status = status;
+        project.history.push(StatusChange {
+            status,
+            at: status::now(),
+        });
+
+        Ok(())
+    }
+
+    /// Takes a project out of the pool without removing it from the workspace.
+    pub fn archive_project(&mut self, name: &str) -> Result<()> {
+        let project = self
+            .get_project(name)
+            .ok_or(RecicionError::new(format!("no project {}", name)))?;
+        if project.archived.is_some() {
+            return Err(RecicionError::new(format!("project {} is already archived", name)).into());
+        }
+        project.archived = Some(status::now());
+
+        Ok(())
+    }
+
+    /// Returns an archived project to the pool.
+    pub fn restore_project(&mut self, name: &str) -> Result<()> {
+        let project = self
+            .get_project(name)
+            .ok_or(RecicionError::new(format!("no project {}", name)))?;
+        if project.archived.take().is_none() {
+            return Err(RecicionError::new(format!("project {} isn't archived", name)).into());
+        }
+
+        Ok(())
+    }
+
+    /// Returns a copy of the workspace without the archived projects, which is the pool that
+    /// rankings are calculated for.
+    ///
+    /// Dependencies on archived projects are dropped along with them.
+    pub fn without_archived(&self) -> Workspace {
+        let mut workspace = self.clone();
+        for project in self.projects.iter().filter(|p| p.is_archived()) {
+            workspace
+                .remove_project(&project.name)
+                .expect("archived project is in the workspace");
+        }
+        workspace
+    }
+
+    /// Returns a copy of the workspace without the done and dropped projects, which are left out
+    /// of rankings unless asked for.
+    ///
+    /// Dependencies on closed projects are dropped along with them, since they no longer block.
+    pub fn without_closed(&self) -> Workspace {
+        let mut workspace = self.clone();
+        for project in self.projects.iter().filter(|p| p.status.is_closed()) {
+            workspace
+                .remove_project(&project.name)
+                .expect("closed project is in the workspace");
+        }
+        workspace
+    }
+
+    /// Moves the named projects to the top of the order, keeping the relative order of the rest.
+    pub fn reorder_projects(&mut self, names: &[String]) -> Result<()> {
+        self.projects = reorder(&self.projects, names, |project| project.get_name())
+            .with_context(|| "reordering projects")?;
+
+        Ok(())
+    }
+
+    /// Resolves a project reference to the index of the project, see [`resolve`].
+    pub fn resolve_project(&self, reference: &str) -> Result<usize> {
+        Ok(resolve(
+            &self.get_project_names(),
+            reference,
+            ItemKind::Project,
+        )?)
+    }
+
+    /// Resolves a project reference to the name of the project, see [`resolve`].
+    pub fn find_project(&self, reference: &str) -> Result<String> {
+        let index = self.resolve_project(reference)?;
+        Ok(self.projects[index].name.clone())
+    }
+
     pub fn get_criterion(&mut self, name: &str) -> Option<&mut Criterion> {
         self.criteria
             .iter_mut()
             .find(|criterion| criterion.name == name)
     }
 
+    pub fn get_criteria(&self) -> &[Criterion] {
+        &self.criteria
+    }
+
+    pub fn get_criterion_names(&self) -> Vec<String> {
+        self.criteria.iter().map(|c| c.name.clone()).collect()
+    }
+
+    /// Resolves a criterion reference to the index of the criterion, see [`resolve`].
+    pub fn resolve_criterion(&self, reference: &str) -> Result<usize> {
+        Ok(resolve(
+            &self.get_criterion_names(),
+            reference,
+            ItemKind::Criterion,
+        )?)
+    }
+
+    /// Resolves a criterion reference to the name of the criterion, see [`resolve`].
+    pub fn find_criterion(&self, reference: &str) -> Result<String> {
+        let index = self.resolve_criterion(reference)?;
+        Ok(self.criteria[index].name.clone())
+    }
+
     pub fn add_priority_set(&mut self, name: &str) -> Result<&mut Self> {
+        validate_name(name)?;
+
         if self.get_priority_set(name).is_some() {
             return Err(RecicionError::new(format!("priority set {} already exists", name)).into());
         }
@@ -101,48 +467,247 @@ impl Workspace {
         self.priority_sets.iter_mut().find(|ps| ps.name == name)
     }
 
-    pub fn activate_priority_set(&mut self, name: &str) -> Result<()> {
-        self.get_priority_set(name)
+    pub fn get_priority_set_names(&self) -> Vec<String> {
+        self.priority_sets
+            .iter()
+            .map(|ps| ps.name.clone())
+            .collect()
+    }
+
+    /// Resolves a priority set reference to the index of the priority set, see [`resolve`].
+    pub fn resolve_priority_set(&self, reference: &str) -> Result<usize> {
+        Ok(resolve(
+            &self.get_priority_set_names(),
+            reference,
+            ItemKind::PrioritySet,
+        )?)
+    }
+
+    /// Resolves a priority set reference to the name of the priority set, see [`resolve`].
+    pub fn find_priority_set(&self, reference: &str) -> Result<String> {
+        let index = self.resolve_priority_set(reference)?;
+        Ok(self.priority_sets[index].name.clone())
+    }
+
+    /// Removes a priority set, deactivating it first if it is the active one.
+    pub fn remove_priority_set(&mut self, name: &str) -> Result<()> {
+        let index = self
+            .priority_sets
+            .iter()
+            .position(|ps| ps.name == name)
             .ok_or(RecicionError::new(format!("no priority set {}", name)))?;
-        self.active_priority_set = Some(name.into());
+        self.priority_sets.remove(index);
+
+        if self.active_priority_set.as_deref() == Some(name) {
+            self.active_priority_set = None;
+        }
+
+        Ok(())
+    }
+
+    /// Moves the named priority sets to the top of the order, keeping the relative order of the
+    /// rest.
+    pub fn reorder_priority_sets(&mut self, names: &[String]) -> Result<()> {
+        self.priority_sets = reorder(&self.priority_sets, names, |ps| ps.get_name())
+            .with_context(|| "reordering priority sets")?;
+
+        Ok(())
+    }
+
+    /// Derives the priorities of a priority set from pairwise comparisons of all criteria and
+    /// stores the comparisons alongside them, see [`ahp`].
+    pub fn set_comparisons(
+        &mut self,
+        priority_set_name: &str,
+        comparisons: Vec<Comparison>,
+    ) -> Result<AhpResult> {
+        let criteria = self.get_criterion_names();
+        let result = ahp::evaluate(&criteria, &comparisons)?;
+
+        let priority_set =
+            self.get_priority_set_mut(priority_set_name)
+                .ok_or(RecicionError::new(format!(
+                    "no priority set {}",
+                    priority_set_name
+                )))?;
+        priority_set.priorities = criteria
+            .into_iter()
+            .zip(result.priorities.iter().cloned())
+            .collect();
+        priority_set.comparisons = comparisons;
+
+        Ok(result)
+    }
+
+    /// Constrains the weights of a criterion in a priority set, replacing any previous
+    /// constraint of the criterion.
+    pub fn set_constraint(
+        &mut self,
+        priority_set_name: &str,
+        criterion_name: &str,
+        constraint: Constraint,
+    ) -> Result<()> {
+        self.get_criterion(criterion_name)
+            .ok_or(RecicionError::new(format!(
+                "no criterion {}",
+                criterion_name
+            )))?;
+
+        self.get_priority_set_mut(priority_set_name)
+            .ok_or(RecicionError::new(format!(
+                "no priority set {}",
+                priority_set_name
+            )))?
+            .constraints
+            .insert(criterion_name.into(), constraint);
+
+        Ok(())
+    }
+
+    pub fn remove_constraint(
+        &mut self,
+        priority_set_name: &str,
+        criterion_name: &str,
+    ) -> Result<()> {
+        self.get_priority_set_mut(priority_set_name)
+            .ok_or(RecicionError::new(format!(
+                "no priority set {}",
+                priority_set_name
+            )))?
+            .constraints
+            .remove(criterion_name)
+            .ok_or(RecicionError::new(format!(
+                "no constraint on criterion {}",
+                criterion_name
+            )))?;
+
         Ok(())
     }
 
-    pub fn write_to_file(&self, path: PathBuf) -> Result<()> {
-        if path.exists() {
-            remove_file(path.clone()).with_context(|| "removing existing worspace file")?;
+    /// Finds the projects that violate a constraint of the active priority set.
+    ///
+    /// Vetoes are ordered by project and then by criterion.
+    pub fn find_vetoes(&self) -> Result<Vec<Veto>> {
+        Ok(self.find_vetoes_for(self.active_priority_set()?))
+    }
+
+    fn find_vetoes_for(&self, priority_set: &PrioritySet) -> Vec<Veto> {
+        let mut vetoes = Vec::new();
+        for (project, weights) in self.projects.iter().zip(self.weight_grid()) {
+            for (criterion, weight) in self.criteria.iter().zip(weights) {
+                let Some(constraint) = priority_set.constraints.get(&criterion.name) else {
+                    continue;
+                };
+                if let Some(reason) = constraint.violation(&weight) {
+                    vetoes.push(Veto {
+                        project: project.name.clone(),
+                        criterion: criterion.name.clone(),
+                        reason,
+                    });
+                }
+            }
+        }
+
+        vetoes
+    }
+
+    /// Returns a copy of the workspace without the projects vetoed by the active priority set,
+    /// which is the pool that rankings under the priority set are calculated for.
+    ///
+    /// Vetoed projects are left out before scoring, so that they don't shift the normalization
+    /// of the other projects. Dependencies on vetoed projects are dropped along with them.
+    pub fn without_vetoed(&self) -> Result<Workspace> {
+        Ok(self.without_vetoed_for(self.active_priority_set()?))
+    }
+
+    /// Returns a copy of the workspace without the projects vetoed by the given priority set, see
+    /// [`Workspace::without_vetoed`].
+    pub(crate) fn without_vetoed_for(&self, priority_set: &PrioritySet) -> Workspace {
+        let vetoed: HashSet<_> = self
+            .find_vetoes_for(priority_set)
+            .into_iter()
+            .map(|veto| veto.project)
+            .collect();
+
+        let mut workspace = self.clone();
+        for project in &vetoed {
+            workspace
+                .remove_project(project)
+                .expect("vetoed project is in the workspace");
         }
+        workspace
+    }
 
-        let toml_string = toml::to_string_pretty(self)?;
-        let mut file = File::create(path.clone()).with_context(|| "creating new workspace file")?;
-        file.write_all(toml_string.as_bytes())?;
+    pub fn get_active_priority_set_name(&self) -> Option<&str> {
+        self.active_priority_set.as_deref()
+    }
 
+    pub fn activate_priority_set(&mut self, name: &str) -> Result<()> {
+        self.get_priority_set(name)
+            .ok_or(RecicionError::new(format!("no priority set {}", name)))?;
+        self.active_priority_set = Some(name.into());
         Ok(())
     }
 
+    /// Writes the workspace atomically, optionally keeping the previous version of the file as
+    /// a backup, see [`storage::write_atomically`].
+    pub fn write_to_file(&self, path: PathBuf, backup: bool) -> Result<()> {
+        storage::write_toml(self, &path, backup).with_context(|| "writing workspace file")
+    }
+
     pub fn read_from_file(path: PathBuf) -> Result<Self> {
         let mut file = File::open(path.clone())
             .with_context(|| format!("opening workspace file {}", path.to_str().unwrap()))?;
         let mut toml_string = String::new();
         file.read_to_string(&mut toml_string)
             .with_context(|| "reading workspace from file")?;
-        let workspace = toml::from_str(toml_string.as_str())
-            .with_context(|| "parsing contents wof workspace file")?;
+        let mut document: toml::Table = toml::from_str(toml_string.as_str())
+            .with_context(|| "parsing contents of workspace file")?;
+        migration::migrate(&mut document).with_context(|| "upgrading workspace file")?;
+        // parsed from text again, since datetimes don't survive deserializing from a table
+        let workspace: Workspace = toml::from_str(&document.to_string())
+            .with_context(|| "parsing contents of workspace file")?;
+        workspace
+            .check_dependencies()
+            .with_context(|| "checking contents of workspace file")?;
 
         Ok(workspace)
     }
 
+    /// Checks that every dependency refers to a project of the workspace, which only a hand
+    /// edited file can break.
+    fn check_dependencies(&self) -> Result<()> {
+        for project in &self.projects {
+            for dependency in &project.dependencies {
+                if !self.projects.iter().any(|p| &p.name == dependency) {
+                    return Err(RecicionError::new(format!(
+                        "project {} depends on unknown project {}",
+                        project.name, dependency
+                    ))
+                    .into());
+                }
+            }
+        }
+        Ok(())
+    }
+
+    pub fn get_schema_version(&self) -> u32 {
+        self.schema_version
+    }
+
     pub fn set_weight(
         &mut self,
         project_name: &str,
         criterion_name: &str,
-        weight: i32,
+        weight: impl Into<Weight>,
     ) -> Result<()> {
+        let weight = weight.into().validated()?;
         self.get_criterion(criterion_name)
             .ok_or(RecicionError::new(format!(
                 "no criterion {}",
                 criterion_name
-            )))?;
+            )))?
+            .validate_weight(&weight)?;
 
         self.get_project(project_name)
             .ok_or(RecicionError::new(format!("no project {}", project_name)))?
@@ -152,19 +717,45 @@ impl Workspace {
         Ok(())
     }
 
-    pub fn get_weight(&mut self, project_name: &str, criterion_name: &str) -> Result<i32> {
+    /// Returns the weight of a project on a criterion, or `None` if the project isn't rated on
+    /// it.
+    pub fn get_weight(
+        &mut self,
+        project_name: &str,
+        criterion_name: &str,
+    ) -> Result<Option<Weight>> {
         self.get_criterion(criterion_name)
             .ok_or(RecicionError::new(format!(
                 "no criterion {}",
                 criterion_name
             )))?;
 
-        Ok(*self
+        Ok(self
             .get_project(project_name)
             .ok_or(RecicionError::new(format!("no project {}", project_name)))?
             .weights
             .get(criterion_name)
-            .unwrap_or(&0))
+            .copied())
+    }
+
+    /// Returns the weight a project is scored with on a criterion, which is the worst weight of
+    /// the criterion if the project isn't rated on it, see [`Workspace::missing_weight`].
+    pub fn get_effective_weight(
+        &mut self,
+        project_name: &str,
+        criterion_name: &str,
+    ) -> Result<Weight> {
+        match self.get_weight(project_name, criterion_name)? {
+            Some(weight) => Ok(weight),
+            None => {
+                let criterion = self
+                    .criteria
+                    .iter()
+                    .find(|criterion| criterion.name == criterion_name)
+                    .expect("criterion was found");
+                Ok(self.missing_weight(criterion))
+            }
+        }
     }
 
     pub fn set_priority(&mut self, criterion_name: &str, priority: f64) -> Result<()> {
@@ -189,89 +780,562 @@ impl Workspace {
         Ok(())
     }
 
-    pub fn calculate_score(&self) -> Result<HashMap<String, f64>> {
-        let priority_set_name = self
-            .active_priority_set
-            .clone()
-            .ok_or(RecicionError::new("no active priority set".into()))?;
-        let priority_set = self
-            .get_priority_set(priority_set_name.as_str())
-            .expect("active priority set should be in the collections of priority sets");
+    /// Returns the priority of a criterion in the active priority set.
+    ///
+    /// Criteria without an explicit priority, or workspaces without an active priority set, fall
+    /// back to the default priority of 1.
+    pub fn get_priority(&self, criterion_name: &str) -> Result<f64> {
+        if !self.criteria.iter().any(|c| c.name == criterion_name) {
+            return Err(RecicionError::new(format!("no criterion {}", criterion_name)).into());
+        }
 
-        let mut result = HashMap::new();
-        self.projects.iter().for_each(|project| {
-            let score = project.calculate_score(&self.criteria, priority_set);
-            result.insert(project.name.clone(), score);
-        });
+        let priority = self
+            .active_priority_set
+            .as_ref()
+            .and_then(|name| self.get_priority_set(name))
+            .and_then(|ps| ps.priorities.get(criterion_name))
+            .unwrap_or(&1.0);
 
-        Ok(result)
+        Ok(*priority)
     }
-}
 
-impl Default for Workspace {
-    fn default() -> Self {
-        Self::new()
+    pub fn get_budget_criterion(&self) -> Option<&str> {
+        self.budget_criterion.as_deref()
     }
-}
 
-#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
-pub struct Project {
-    name: String,
-    weights: HashMap<String, i32>,
-}
+    /// Designates the criterion whose weights are the costs of the projects in portfolios.
+    pub fn set_budget_criterion(&mut self, criterion_name: Option<&str>) -> Result<()> {
+        if let Some(criterion_name) = criterion_name {
+            self.get_criterion(criterion_name)
+                .ok_or(RecicionError::new(format!(
+                    "no criterion {}",
+                    criterion_name
+                )))?;
+        }
 
-impl Project {
-    pub fn new(name: &str) -> Self {
-        Self {
-            name: String::from(name),
-            weights: HashMap::new(),
+        self.budget_criterion = criterion_name.map(String::from);
+        Ok(())
+    }
+
+    /// Records the current scores of the projects in the pool under every priority set.
+    pub fn take_snapshot(&mut self, label: &str) -> Result<()> {
+        if self.priority_sets.is_empty() {
+            return Err(RecicionError::new("no priority sets in workspace".into()).into());
         }
+        if self.snapshots.iter().any(|s| s.get_label() == label) {
+            return Err(RecicionError::new(format!("snapshot {} already exists", label)).into());
+        }
+
+        let snapshot = Snapshot::take(self, label)?;
+        self.snapshots.push(snapshot);
+
+        Ok(())
     }
 
-    pub fn get_name(&self) -> &str {
-        self.name.as_str()
+    pub fn get_snapshots(&self) -> &[Snapshot] {
+        &self.snapshots
     }
 
-    fn calculate_score(&self, criteria: &[Criterion], priority_set: &PrioritySet) -> f64 {
-        let mut score = 0.0;
-        criteria.iter().for_each(|criterion| {
-            // TODO define default priority in the configuration
-            let priority = priority_set.priorities.get(&criterion.name).unwrap_or(&1.0);
-            let weight = self.weights.get(&criterion.name).unwrap_or(&0);
+    pub fn get_scoring_method(&self) -> Method {
+        self.scoring_method
+    }
 
-            score += *weight as f64 * priority;
-        });
+    pub fn set_scoring_method(&mut self, method: Method) {
+        self.scoring_method = method;
+    }
 
-        score
+    /// Calculates the scores with the scoring method selected for the workspace.
+    pub fn calculate_score(&self) -> Result<HashMap<String, f64>> {
+        self.calculate_score_with(self.scoring_method.scoring_method())
     }
-}
 
-#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
-pub struct Criterion {
-    name: String,
-}
+    /// Calculates the scores under the active priority set with the given scoring method.
+    ///
+    /// Uncertain weights contribute their expected value.
+    pub fn calculate_score_with(&self, method: &dyn ScoringMethod) -> Result<HashMap<String, f64>> {
+        self.check_scoring_method(method)?;
+        let (weights, priorities) = self.scoring_input(Scenario::Expected)?;
+        let scores = method.score(&weights, &priorities)?;
 
-impl Criterion {
-    pub fn new(name: &str) -> Self {
-        Self {
-            name: String::from(name),
-        }
+        Ok(self.get_project_names().into_iter().zip(scores).collect())
     }
-}
 
-#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
-pub struct PrioritySet {
-    name: String,
-    priorities: HashMap<String, f64>,
-}
+    /// Calculates the worst, expected and best scores with the scoring method of the workspace.
+    pub fn calculate_score_range(&self) -> Result<HashMap<String, ScoreRange>> {
+        self.calculate_score_range_with(self.scoring_method.scoring_method())
+    }
+
+    /// Calculates the worst, expected and best scores under the active priority set.
+    ///
+    /// The worst and best scores are calculated in scenarios where every uncertain weight takes
+    /// the end of its range that lowers or raises the score. For the normalizing scoring methods
+    /// this assumes that the weights of all projects move together.
+    pub fn calculate_score_range_with(
+        &self,
+        method: &dyn ScoringMethod,
+    ) -> Result<HashMap<String, ScoreRange>> {
+        self.check_scoring_method(method)?;
+
+        let mut scenario_scores = Vec::new();
+        for scenario in [
+            Scenario::Pessimistic,
+            Scenario::Expected,
+            Scenario::Optimistic,
+        ] {
+            let (weights, priorities) = self.scoring_input(scenario)?;
+            scenario_scores.push(method.score(&weights, &priorities)?);
+        }
+
+        Ok(self
+            .get_project_names()
+            .into_iter()
+            .enumerate()
+            .map(|(i, name)| {
+                let scores = [
+                    scenario_scores[0][i],
+                    scenario_scores[1][i],
+                    scenario_scores[2][i],
+                ];
+                let range = ScoreRange {
+                    worst: scores.iter().cloned().fold(f64::INFINITY, f64::min),
+                    expected: scores[1],
+                    best: scores.iter().cloned().fold(f64::NEG_INFINITY, f64::max),
+                };
+                (name, range)
+            })
+            .collect())
+    }
+
+    /// Returns whether any weight in the grid is uncertain.
+    pub fn has_uncertain_weights(&self) -> bool {
+        self.projects
+            .iter()
+            .any(|project| project.weights.values().any(|weight| !weight.is_exact()))
+    }
+
+    /// Evaluates the projects under the active priority set with TOPSIS, see [`Topsis`].
+    pub fn calculate_topsis(&self) -> Result<HashMap<String, TopsisResult>> {
+        let (weights, priorities) = self.scoring_input(Scenario::Expected)?;
+        let results = Topsis.evaluate(&weights, &priorities);
+
+        Ok(self.get_project_names().into_iter().zip(results).collect())
+    }
+
+    fn active_priority_set(&self) -> Result<&PrioritySet> {
+        let priority_set_name = self
+            .active_priority_set
+            .as_ref()
+            .ok_or(RecicionError::new("no active priority set".into()))?;
+
+        Ok(self
+            .get_priority_set(priority_set_name)
+            .expect("active priority set should be in the collections of priority sets"))
+    }
+
+    /// Returns the weight grid with one row per project and the priorities of the active
+    /// priority set, both in the order of the criteria.
+    fn scoring_input(&self, scenario: Scenario) -> Result<(Vec<Vec<f64>>, Vec<f64>)> {
+        Ok(self.scoring_input_for(self.active_priority_set()?, scenario))
+    }
+
+    /// Returns the weight grid and the priorities like [`Workspace::scoring_input`], but for any
+    /// priority set.
+    fn scoring_input_for(
+        &self,
+        priority_set: &PrioritySet,
+        scenario: Scenario,
+    ) -> (Vec<Vec<f64>>, Vec<f64>) {
+        let priorities = priority_set.priority_vector(&self.criteria);
+        let weights = self
+            .weight_grid()
+            .iter()
+            .map(|row| oriented_weights(&self.criteria, row, &priorities, scenario))
+            .collect();
+
+        (weights, priorities)
+    }
+
+    /// Returns the weights of every project in the order of the criteria, with the missing
+    /// weights filled in, see [`Workspace::missing_weight`].
+    fn weight_grid(&self) -> Vec<Vec<Weight>> {
+        let missing: Vec<_> = self
+            .criteria
+            .iter()
+            .map(|criterion| self.missing_weight(criterion))
+            .collect();
+
+        self.projects
+            .iter()
+            .map(|project| {
+                self.criteria
+                    .iter()
+                    .zip(&missing)
+                    .map(|(criterion, missing)| {
+                        project
+                            .weights
+                            .get(&criterion.name)
+                            .copied()
+                            .unwrap_or(*missing)
+                    })
+                    .collect()
+            })
+            .collect()
+    }
+
+    /// Returns the weight that projects are scored with on a criterion they have no weight on.
+    ///
+    /// Missing weights count as the worst weight of the criterion: the lower end of the scale of
+    /// benefit criteria, or 0 if the scale is open, and the upper end of the scale of cost
+    /// criteria. Cost criteria without an upper end take the highest weight of any project.
+    fn missing_weight(&self, criterion: &Criterion) -> Weight {
+        let worst = match (criterion.direction, criterion.max) {
+            (Direction::Benefit, _) => criterion.min.unwrap_or_default(),
+            (Direction::Cost, Some(max)) => max,
+            (Direction::Cost, None) => self
+                .projects
+                .iter()
+                .filter_map(|project| project.weights.get(&criterion.name))
+                .map(Weight::max)
+                .reduce(f64::max)
+                .unwrap_or(criterion.min.unwrap_or_default()),
+        };
+
+        Weight::Exact(worst)
+    }
+
+    /// Checks that a scoring method can score the criteria of the workspace.
+    fn check_scoring_method(&self, method: &dyn ScoringMethod) -> Result<()> {
+        if !method.requires_non_negative_weights() {
+            return Ok(());
+        }
+
+        // weights of cost criteria are negated unless their scale is bounded on both sides
+        match self
+            .criteria
+            .iter()
+            .find(|c| c.direction == Direction::Cost && (c.min.is_none() || c.max.is_none()))
+        {
+            Some(criterion) => Err(RecicionError::new(format!(
+                "the weighted product requires a scale bounded on both sides for cost criterion {}",
+                criterion.name
+            ))
+            .into()),
+            None => Ok(()),
+        }
+    }
+}
+
+impl Default for Workspace {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Project {
+    name: String,
+    weights: HashMap<String, Weight>,
+    /// Prerequisites that have to be done before the project can start.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    dependencies: Vec<String>,
+    #[serde(default)]
+    status: Status,
+    /// Changes of the status, from the oldest to the newest.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    history: Vec<StatusChange>,
+    /// When the project was archived, archived projects are left out of rankings.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    archived: Option<Datetime>,
+}
+
+impl Project {
+    pub fn new(name: &str) -> Self {
+        Self {
+            name: String::from(name),
+            weights: HashMap::new(),
+            dependencies: Vec::new(),
+            status: Status::default(),
+            history: vec![StatusChange {
+                status: Status::default(),
+                at: status::now(),
+            }],
+            archived: None,
+        }
+    }
+
+    pub fn get_name(&self) -> &str {
+        self.name.as_str()
+    }
+
+    pub fn get_dependencies(&self) -> &[String] {
+        &self.dependencies
+    }
+
+    pub fn get_status(&self) -> Status {
+        self.status
+    }
+
+    pub fn get_history(&self) -> &[StatusChange] {
+        &self.history
+    }
+
+    pub fn get_archived(&self) -> Option<&Datetime> {
+        self.archived.as_ref()
+    }
+
+    pub fn is_archived(&self) -> bool {
+        self.archived.is_some()
+    }
+}
+
+/// Values uncertain weights take when scoring.
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Scenario {
+    Pessimistic,
+    Expected,
+    Optimistic,
+}
+
+/// Returns a row of weights oriented so that higher is better for every criterion, see
+/// [`Criterion::orient`].
+///
+/// Uncertain weights take the value of the scenario, where the favorable end of the range is the
+/// one that raises the score given the sign of the priority.
+fn oriented_weights(
+    criteria: &[Criterion],
+    weights: &[Weight],
+    priorities: &[f64],
+    scenario: Scenario,
+) -> Vec<f64> {
+    criteria
+        .iter()
+        .zip(weights)
+        .zip(priorities)
+        .map(|((criterion, weight), priority)| {
+            let bounds = (
+                criterion.orient(weight.min()),
+                criterion.orient(weight.max()),
+            );
+            let (low, high) = (bounds.0.min(bounds.1), bounds.0.max(bounds.1));
+            match (scenario, *priority >= 0.0) {
+                (Scenario::Expected, _) => criterion.orient(weight.expected()),
+                (Scenario::Optimistic, true) | (Scenario::Pessimistic, false) => high,
+                (Scenario::Optimistic, false) | (Scenario::Pessimistic, true) => low,
+            }
+        })
+        .collect()
+}
+
+/// Whether higher weights of a criterion are better or worse.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum Direction {
+    #[default]
+    Benefit,
+    Cost,
+}
+
+impl Display for Direction {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            Direction::Benefit => write!(f, "benefit"),
+            Direction::Cost => write!(f, "cost"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Criterion {
+    name: String,
+    #[serde(default)]
+    direction: Direction,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    min: Option<f64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    max: Option<f64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    unit: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    description: Option<String>,
+}
+
+impl Criterion {
+    pub fn new(name: &str) -> Self {
+        Self {
+            name: String::from(name),
+            direction: Direction::default(),
+            min: None,
+            max: None,
+            unit: None,
+            description: None,
+        }
+    }
+
+    pub fn get_name(&self) -> &str {
+        self.name.as_str()
+    }
+
+    pub fn get_direction(&self) -> Direction {
+        self.direction
+    }
+
+    pub fn set_direction(&mut self, direction: Direction) {
+        self.direction = direction;
+    }
+
+    pub fn get_scale(&self) -> (Option<f64>, Option<f64>) {
+        (self.min, self.max)
+    }
+
+    /// Sets the range of valid weights; either bound may be left open.
+    ///
+    /// Use [`Workspace::set_criterion_scale`] to change the scale of a criterion that is already
+    /// part of a workspace, so that existing weights are checked against the new scale.
+    pub fn set_scale(&mut self, min: Option<f64>, max: Option<f64>) -> Result<()> {
+        if let (Some(min), Some(max)) = (min, max) {
+            if min > max {
+                return Err(RecicionError::new(format!(
+                    "minimum {} of criterion {} is greater than its maximum {}",
+                    min, self.name, max
+                ))
+                .into());
+            }
+        }
+
+        self.min = min;
+        self.max = max;
+        Ok(())
+    }
+
+    pub fn get_unit(&self) -> Option<&str> {
+        self.unit.as_deref()
+    }
+
+    pub fn set_unit(&mut self, unit: Option<String>) {
+        self.unit = unit;
+    }
+
+    pub fn get_description(&self) -> Option<&str> {
+        self.description.as_deref()
+    }
+
+    pub fn set_description(&mut self, description: Option<String>) {
+        self.description = description;
+    }
+
+    /// Checks that a weight lies within the scale of the criterion.
+    pub fn validate_weight(&self, weight: &Weight) -> Result<()> {
+        let below = self.min.is_some_and(|min| weight.min() < min);
+        let above = self.max.is_some_and(|max| weight.max() > max);
+        if below || above {
+            return Err(RecicionError::new(format!(
+                "weight {} is outside of the scale of criterion {} from {} to {}",
+                weight,
+                self.name,
+                self.min.map_or("-inf".into(), |min| min.to_string()),
+                self.max.map_or("inf".into(), |max| max.to_string()),
+            ))
+            .into());
+        }
+
+        Ok(())
+    }
+
+    /// Turns a weight into a value where higher is better.
+    ///
+    /// Weights of cost criteria are mirrored within their scale if it is bounded on both sides
+    /// and negated otherwise.
+    fn orient(&self, weight: f64) -> f64 {
+        match (self.direction, self.min, self.max) {
+            (Direction::Benefit, _, _) => weight,
+            (Direction::Cost, Some(min), Some(max)) => min + max - weight,
+            (Direction::Cost, _, _) => -weight,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PrioritySet {
+    name: String,
+    priorities: HashMap<String, f64>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    comparisons: Vec<Comparison>,
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    constraints: HashMap<String, Constraint>,
+}
 
 impl PrioritySet {
     pub fn new(name: &str) -> Self {
         Self {
             name: String::from(name),
             priorities: HashMap::new(),
+            comparisons: Vec::new(),
+            constraints: HashMap::new(),
+        }
+    }
+
+    pub fn get_name(&self) -> &str {
+        self.name.as_str()
+    }
+
+    /// Returns the pairwise comparisons the priorities were derived from, if any.
+    pub fn get_comparisons(&self) -> &[Comparison] {
+        &self.comparisons
+    }
+
+    pub fn get_constraint(&self, criterion_name: &str) -> Option<&Constraint> {
+        self.constraints.get(criterion_name)
+    }
+
+    /// Returns the priorities of the given criteria in their order.
+    fn priority_vector(&self, criteria: &[Criterion]) -> Vec<f64> {
+        criteria
+            .iter()
+            // TODO define default priority in the configuration
+            .map(|criterion| *self.priorities.get(&criterion.name).unwrap_or(&1.0))
+            .collect()
+    }
+}
+
+/// Checks that a name can't be mistaken for a numeric ID.
+fn validate_name(name: &str) -> Result<()> {
+    if !name.chars().any(char::is_alphabetic) {
+        return Err(RecicionError::new(format!(
+            "name '{}' must contain at least one alphabetic character",
+            name
+        ))
+        .into());
+    }
+
+    Ok(())
+}
+
+/// Builds a new order in which the named items come first, followed by the remaining items in
+/// their previous order.
+fn reorder<T: Clone>(
+    items: &[T],
+    names: &[String],
+    name_of: impl Fn(&T) -> &str,
+) -> Result<Vec<T>> {
+    let mut reordered = Vec::with_capacity(items.len());
+
+    for name in names {
+        let item = items
+            .iter()
+            .find(|item| name_of(item) == name)
+            .ok_or(RecicionError::new(format!("no item {}", name)))?;
+        if reordered.iter().any(|other| name_of(other) == name) {
+            return Err(RecicionError::new(format!("{} is listed more than once", name)).into());
         }
+        reordered.push(item.clone());
     }
+
+    items
+        .iter()
+        .filter(|item| !names.iter().any(|name| name == name_of(item)))
+        .for_each(|item| reordered.push(item.clone()));
+
+    Ok(reordered)
 }
 
 #[cfg(test)]
@@ -297,7 +1361,9 @@ mod tests {
 
         workspace
             .add_criterion(Criterion::new("Fun"))
-            .add_criterion(Criterion::new("Useful"));
+            .unwrap()
+            .add_criterion(Criterion::new("Useful"))
+            .unwrap();
 
         workspace
             .add_priority_set("Workday")
@@ -326,7 +1392,7 @@ mod tests {
         let temp_file = NamedTempFile::new().unwrap();
         let workspace = build_test_workspace();
         workspace
-            .write_to_file(temp_file.path().to_path_buf())
+            .write_to_file(temp_file.path().to_path_buf(), false)
             .unwrap();
     }
 
@@ -335,7 +1401,23 @@ mod tests {
         let temp_file = NamedTempFile::new().unwrap();
         let workspace = build_test_workspace();
         workspace
-            .write_to_file(temp_file.path().to_path_buf())
+            .write_to_file(temp_file.path().to_path_buf(), false)
+            .unwrap();
+
+        let reconstructed_workspace =
+            Workspace::read_from_file(temp_file.path().to_path_buf()).unwrap();
+
+        assert_eq!(workspace, reconstructed_workspace);
+    }
+
+    #[test]
+    fn test_reconstruct_status_from_file() {
+        let temp_file = NamedTempFile::new().unwrap();
+        let mut workspace = build_test_workspace();
+        workspace.set_status("Project 1", Status::Active).unwrap();
+        workspace.archive_project("Project 2").unwrap();
+        workspace
+            .write_to_file(temp_file.path().to_path_buf(), false)
             .unwrap();
 
         let reconstructed_workspace =
@@ -369,13 +1451,26 @@ mod tests {
         let mut ws = build_test_workspace();
         let weight = 1;
         ws.set_weight("Project 1", "Fun", weight).unwrap();
-        assert_eq!(weight, ws.get_weight("Project 1", "Fun").unwrap());
+        assert_eq!(
+            Some(Weight::from(weight)),
+            ws.get_weight("Project 1", "Fun").unwrap()
+        );
     }
 
     #[test]
-    fn test_get_default_weight() {
+    fn test_get_missing_weight() {
         let mut ws = build_test_workspace();
-        assert_eq!(0, ws.get_weight("Project 1", "Fun").unwrap());
+        assert_eq!(None, ws.get_weight("Project 1", "Fun").unwrap());
+        assert_eq!(
+            Weight::from(0),
+            ws.get_effective_weight("Project 1", "Fun").unwrap()
+        );
+
+        ws.set_criterion_scale("Fun", Some(1.0), Some(5.0)).unwrap();
+        assert_eq!(
+            Weight::from(1),
+            ws.get_effective_weight("Project 1", "Fun").unwrap()
+        );
     }
 
     #[test]
@@ -415,4 +1510,645 @@ mod tests {
         assert_eq!(*scores.get("Project 1").unwrap(), 1.0);
         assert_eq!(*scores.get("Project 2").unwrap(), 4.0);
     }
+
+    #[test]
+    fn test_add_criterion_twice() {
+        let mut ws = build_test_workspace();
+        let result = ws.add_criterion(Criterion::new("Fun"));
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_add_criterion_invalid_name() {
+        let mut ws = build_test_workspace();
+        let result = ws.add_criterion(Criterion::new("42"));
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_add_project_invalid_name() {
+        let mut ws = build_test_workspace();
+        let result = ws.add_project(Project::new("2"));
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_find_criterion() {
+        let ws = build_test_workspace();
+        assert_eq!("Useful", ws.find_criterion("Useful").unwrap());
+        assert_eq!("Useful", ws.find_criterion("2").unwrap());
+        assert!(ws.find_criterion("3").is_err());
+        assert!(ws.find_criterion("0").is_err());
+    }
+
+    #[test]
+    fn test_remove_criterion() {
+        let mut ws = build_test_workspace();
+        ws.set_weight("Project 1", "Fun", 1).unwrap();
+        ws.remove_criterion("Fun").unwrap();
+
+        assert_eq!(vec!["Useful"], ws.get_criterion_names());
+        assert!(ws.get_weight("Project 1", "Fun").is_err());
+        assert!(!ws.projects[0].weights.contains_key("Fun"));
+        assert!(ws
+            .priority_sets
+            .iter()
+            .all(|ps| !ps.priorities.contains_key("Fun")));
+    }
+
+    #[test]
+    fn test_reorder_criteria() {
+        let mut ws = build_test_workspace();
+        ws.add_criterion(Criterion::new("Cheap")).unwrap();
+
+        ws.reorder_criteria(&["Cheap".into()]).unwrap();
+        assert_eq!(vec!["Cheap", "Fun", "Useful"], ws.get_criterion_names());
+
+        ws.reorder_criteria(&["Useful".into(), "Fun".into()])
+            .unwrap();
+        assert_eq!(vec!["Useful", "Fun", "Cheap"], ws.get_criterion_names());
+
+        assert!(ws.reorder_criteria(&["Funn".into()]).is_err());
+        assert!(ws.reorder_criteria(&["Fun".into(), "Fun".into()]).is_err());
+    }
+
+    #[test]
+    fn test_get_priority() {
+        let mut ws = build_test_workspace();
+        ws.add_criterion(Criterion::new("Cheap")).unwrap();
+
+        assert_eq!(1.0, ws.get_priority("Useful").unwrap());
+        assert_eq!(1.0, ws.get_priority("Cheap").unwrap());
+        assert!(ws.get_priority("Funn").is_err());
+    }
+
+    #[test]
+    fn test_add_priority_set_twice() {
+        let mut ws = build_test_workspace();
+        assert!(ws.add_priority_set("Workday").is_err());
+        assert!(ws.add_priority_set("7").is_err());
+    }
+
+    #[test]
+    fn test_find_priority_set() {
+        let ws = build_test_workspace();
+        assert_eq!("Weekend", ws.find_priority_set("Weekend").unwrap());
+        assert_eq!("Workday", ws.find_priority_set("1").unwrap());
+        assert!(ws.find_priority_set("3").is_err());
+    }
+
+    #[test]
+    fn test_remove_active_priority_set() {
+        let mut ws = build_test_workspace();
+        assert_eq!(Some("Weekend"), ws.get_active_priority_set_name());
+
+        ws.remove_priority_set("Weekend").unwrap();
+
+        assert_eq!(vec!["Workday"], ws.get_priority_set_names());
+        assert_eq!(None, ws.get_active_priority_set_name());
+        assert!(ws.calculate_score().is_err());
+    }
+
+    #[test]
+    fn test_remove_inactive_priority_set() {
+        let mut ws = build_test_workspace();
+        ws.remove_priority_set("Workday").unwrap();
+
+        assert_eq!(vec!["Weekend"], ws.get_priority_set_names());
+        assert_eq!(Some("Weekend"), ws.get_active_priority_set_name());
+        assert!(ws.remove_priority_set("Workday").is_err());
+    }
+
+    #[test]
+    fn test_reorder_priority_sets() {
+        let mut ws = build_test_workspace();
+        ws.reorder_priority_sets(&["Weekend".into()]).unwrap();
+        assert_eq!(vec!["Weekend", "Workday"], ws.get_priority_set_names());
+    }
+
+    #[test]
+    fn test_find_project() {
+        let ws = build_test_workspace();
+        assert_eq!("Project 2", ws.find_project("Project 2").unwrap());
+        assert_eq!("Project =", ws.find_project("3").unwrap());
+        assert!(ws.find_project("6").is_err());
+        assert!(ws.find_project("Project 3").is_err());
+    }
+
+    #[test]
+    fn test_find_project_case_insensitive() {
+        let mut ws = build_test_workspace();
+        ws.add_project(Project::new("Garden")).unwrap();
+
+        assert_eq!(5, ws.resolve_project("garden").unwrap());
+        assert_eq!("Garden", ws.find_project("gar").unwrap());
+        assert!(ws.find_project("project").is_err());
+    }
+
+    #[test]
+    fn test_remove_project() {
+        let mut ws = build_test_workspace();
+        ws.set_weight("Project 2", "Fun", 1).unwrap();
+        ws.remove_project("Project 2").unwrap();
+
+        assert!(ws.find_project("Project 2").is_err());
+        assert_eq!(4, ws.get_project_names().len());
+        assert!(ws.remove_project("Project 2").is_err());
+    }
+
+    #[test]
+    fn test_reorder_projects() {
+        let mut ws = build_test_workspace();
+        ws.reorder_projects(&["Project =".into(), "Project 2".into()])
+            .unwrap();
+
+        assert_eq!(
+            vec![
+                "Project =",
+                "Project 2",
+                "Project 1",
+                "Project [toml]",
+                "Project\nNewline"
+            ],
+            ws.get_project_names()
+        );
+    }
+
+    #[test]
+    fn test_calculate_score_with_workspace_method() {
+        let mut ws = build_test_workspace();
+        ws.set_weight("Project 1", "Fun", 1).unwrap();
+        ws.set_weight("Project 2", "Fun", 3).unwrap();
+        ws.set_weight("Project 2", "Useful", 100).unwrap();
+
+        ws.set_scoring_method(Method::MinMax);
+        let scores = ws.calculate_score().unwrap();
+
+        assert_eq!(*scores.get("Project 1").unwrap(), 1.0 / 3.0 * 2.0);
+        assert_eq!(*scores.get("Project 2").unwrap(), 2.0 + 1.0);
+        assert_eq!(*scores.get("Project =").unwrap(), 0.0);
+    }
+
+    #[test]
+    fn test_read_workspace_without_scoring_method() {
+        let toml_string = "projects = []\ncriteria = []\npriority_sets = []\n";
+        let ws: Workspace = toml::from_str(toml_string).unwrap();
+        assert_eq!(Method::WeightedSum, ws.get_scoring_method());
+
+        let toml_string = format!("{}scoring_method = \"z-score\"\n", toml_string);
+        let ws: Workspace = toml::from_str(&toml_string).unwrap();
+        assert_eq!(Method::ZScore, ws.get_scoring_method());
+    }
+
+    #[test]
+    fn test_calculate_topsis() {
+        let mut ws = build_test_workspace();
+        ws.set_weight("Project 1", "Fun", 2).unwrap();
+        ws.set_weight("Project 1", "Useful", 2).unwrap();
+        ws.set_weight("Project 2", "Fun", 1).unwrap();
+
+        let results = ws.calculate_topsis().unwrap();
+        let best = results.get("Project 1").unwrap();
+        let worst = results.get("Project =").unwrap();
+
+        assert_eq!(0.0, best.ideal_distance);
+        assert_eq!(1.0, best.closeness);
+        assert_eq!(0.0, worst.anti_ideal_distance);
+        assert_eq!(0.0, worst.closeness);
+        assert!(results.get("Project 2").unwrap().closeness > 0.0);
+
+        ws.set_scoring_method(Method::Topsis);
+        let scores = ws.calculate_score().unwrap();
+        assert_eq!(1.0, *scores.get("Project 1").unwrap());
+    }
+
+    #[test]
+    fn test_set_comparisons() {
+        let mut ws = build_test_workspace();
+        let comparisons = vec![Comparison::new("Useful", "Fun", 3.0).unwrap()];
+
+        let result = ws.set_comparisons("Workday", comparisons.clone()).unwrap();
+        assert_eq!(vec![0.25, 0.75], result.priorities);
+
+        ws.activate_priority_set("Workday").unwrap();
+        assert_eq!(0.25, ws.get_priority("Fun").unwrap());
+        assert_eq!(0.75, ws.get_priority("Useful").unwrap());
+        assert_eq!(
+            comparisons,
+            ws.get_priority_set("Workday").unwrap().get_comparisons()
+        );
+
+        ws.remove_criterion("Fun").unwrap();
+        assert!(ws
+            .get_priority_set("Workday")
+            .unwrap()
+            .get_comparisons()
+            .is_empty());
+    }
+
+    #[test]
+    fn test_set_incomplete_comparisons() {
+        let mut ws = build_test_workspace();
+        ws.add_criterion(Criterion::new("Cheap")).unwrap();
+        let comparisons = vec![Comparison::new("Useful", "Fun", 3.0).unwrap()];
+
+        assert!(ws.set_comparisons("Workday", comparisons).is_err());
+        assert!(ws
+            .get_priority_set("Workday")
+            .unwrap()
+            .get_comparisons()
+            .is_empty());
+    }
+
+    #[test]
+    fn test_set_weight_outside_of_scale() {
+        let mut ws = build_test_workspace();
+        ws.set_criterion_scale("Fun", Some(0.0), Some(5.0)).unwrap();
+
+        ws.set_weight("Project 1", "Fun", 5).unwrap();
+        assert!(ws.set_weight("Project 1", "Fun", 6).is_err());
+        assert!(ws.set_weight("Project 1", "Fun", -1).is_err());
+        assert_eq!(
+            Some(Weight::from(5)),
+            ws.get_weight("Project 1", "Fun").unwrap()
+        );
+    }
+
+    #[test]
+    fn test_set_scale_with_existing_weights() {
+        let mut ws = build_test_workspace();
+        ws.set_weight("Project 1", "Fun", 7).unwrap();
+
+        assert!(ws.set_criterion_scale("Fun", Some(0.0), Some(5.0)).is_err());
+        assert!(ws.set_criterion_scale("Fun", Some(5.0), Some(0.0)).is_err());
+        assert_eq!((None, None), ws.get_criterion("Fun").unwrap().get_scale());
+
+        ws.set_criterion_scale("Fun", None, Some(10.0)).unwrap();
+        assert_eq!(
+            (None, Some(10.0)),
+            ws.get_criterion("Fun").unwrap().get_scale()
+        );
+    }
+
+    #[test]
+    fn test_calculate_score_with_cost_criteria() {
+        let mut ws = build_test_workspace();
+        ws.get_criterion("Fun")
+            .unwrap()
+            .set_direction(Direction::Cost);
+        ws.set_weight("Project 1", "Fun", 1).unwrap();
+        ws.set_weight("Project 2", "Fun", 3).unwrap();
+
+        let scores = ws.calculate_score().unwrap();
+        assert_eq!(-2.0, *scores.get("Project 1").unwrap());
+        assert_eq!(-6.0, *scores.get("Project 2").unwrap());
+        // unrated projects are as costly as the costliest rated project
+        assert_eq!(-6.0, *scores.get("Project =").unwrap());
+
+        ws.set_criterion_scale("Fun", Some(0.0), Some(5.0)).unwrap();
+
+        let scores = ws.calculate_score().unwrap();
+        assert_eq!(8.0, *scores.get("Project 1").unwrap());
+        assert_eq!(4.0, *scores.get("Project 2").unwrap());
+        // unrated projects take the top of the scale, which is the worst cost
+        assert_eq!(0.0, *scores.get("Project =").unwrap());
+    }
+
+    #[test]
+    fn test_missing_weight_is_worst_of_scale() {
+        let mut ws = build_test_workspace();
+        ws.set_criterion_scale("Fun", Some(1.0), Some(5.0)).unwrap();
+        ws.set_weight("Project 1", "Fun", 2).unwrap();
+
+        let scores = ws.calculate_score().unwrap();
+        assert_eq!(4.0, *scores.get("Project 1").unwrap());
+        assert_eq!(2.0, *scores.get("Project 2").unwrap());
+    }
+
+    #[test]
+    fn test_weighted_product_with_cost_criteria() {
+        let mut ws = build_test_workspace();
+        ws.get_criterion("Fun")
+            .unwrap()
+            .set_direction(Direction::Cost);
+        ws.set_scoring_method(Method::WeightedProduct);
+
+        let error = ws.calculate_score().unwrap_err();
+        assert!(error.to_string().contains("cost criterion Fun"));
+
+        ws.set_criterion_scale("Fun", Some(0.0), Some(5.0)).unwrap();
+        ws.set_weight("Project 1", "Fun", 1).unwrap();
+        ws.set_weight("Project 1", "Useful", 1).unwrap();
+
+        let scores = ws.calculate_score().unwrap();
+        assert_eq!(16.0, *scores.get("Project 1").unwrap());
+    }
+
+    #[test]
+    fn test_read_criterion_without_details() {
+        let criterion: Criterion = toml::from_str("name = \"Fun\"").unwrap();
+        assert_eq!(Criterion::new("Fun"), criterion);
+
+        let criterion: Criterion =
+            toml::from_str("name = \"Effort\"\ndirection = \"cost\"\nmin = 0.0\nmax = 5.0")
+                .unwrap();
+        assert_eq!(Direction::Cost, criterion.get_direction());
+        assert_eq!((Some(0.0), Some(5.0)), criterion.get_scale());
+    }
+
+    #[test]
+    fn test_set_uncertain_weight_outside_of_scale() {
+        let mut ws = build_test_workspace();
+        ws.set_criterion_scale("Fun", Some(0.0), Some(5.0)).unwrap();
+
+        ws.set_weight("Project 1", "Fun", Weight::range(1.0, 5.0).unwrap())
+            .unwrap();
+        let result = ws.set_weight(
+            "Project 1",
+            "Fun",
+            Weight::triangular(1.0, 2.0, 6.0).unwrap(),
+        );
+        assert!(result.is_err());
+        assert!(ws
+            .set_weight("Project 1", "Fun", Weight::Range { min: 3.0, max: 2.0 })
+            .is_err());
+    }
+
+    #[test]
+    fn test_calculate_score_range() {
+        let mut ws = build_test_workspace();
+        ws.set_weight("Project 1", "Fun", 2.5).unwrap();
+        ws.set_weight("Project 1", "Useful", Weight::range(1.0, 4.0).unwrap())
+            .unwrap();
+        ws.set_weight(
+            "Project 2",
+            "Fun",
+            Weight::triangular(0.0, 1.0, 5.0).unwrap(),
+        )
+        .unwrap();
+        assert!(ws.has_uncertain_weights());
+
+        let scores = ws.calculate_score_range().unwrap();
+        let range = scores.get("Project 1").unwrap();
+        assert_eq!(6.0, range.worst);
+        assert_eq!(7.5, range.expected);
+        assert_eq!(9.0, range.best);
+
+        let range = scores.get("Project 2").unwrap();
+        assert_eq!(0.0, range.worst);
+        assert_eq!(4.0, range.expected);
+        assert_eq!(10.0, range.best);
+
+        assert_eq!(
+            7.5,
+            *ws.calculate_score().unwrap().get("Project 1").unwrap()
+        );
+    }
+
+    #[test]
+    fn test_calculate_score_range_with_cost_and_negative_priority() {
+        let mut ws = build_test_workspace();
+        ws.get_criterion("Fun")
+            .unwrap()
+            .set_direction(Direction::Cost);
+        ws.set_priority("Useful", -1.0).unwrap();
+        ws.set_weight("Project 1", "Fun", Weight::range(1.0, 3.0).unwrap())
+            .unwrap();
+        ws.set_weight("Project 1", "Useful", Weight::range(1.0, 2.0).unwrap())
+            .unwrap();
+
+        let range = *ws
+            .calculate_score_range()
+            .unwrap()
+            .get("Project 1")
+            .unwrap();
+        assert_eq!(-6.0 - 2.0, range.worst);
+        assert_eq!(-4.0 - 1.5, range.expected);
+        assert_eq!(-2.0 - 1.0, range.best);
+    }
+
+    #[test]
+    fn test_find_vetoes() {
+        let mut ws = build_test_workspace();
+        ws.set_weight("Project 1", "Fun", 5).unwrap();
+        ws.set_weight("Project 2", "Useful", 3).unwrap();
+        ws.set_constraint("Weekend", "Fun", Constraint::new(None, Some(3.0)).unwrap())
+            .unwrap();
+        ws.set_constraint(
+            "Workday",
+            "Useful",
+            Constraint::new(Some(2.0), None).unwrap(),
+        )
+        .unwrap();
+
+        let vetoes = ws.find_vetoes().unwrap();
+        assert_eq!(1, vetoes.len());
+        assert_eq!("Project 1", vetoes[0].project);
+        assert_eq!("Fun: 5 is above the maximum of 3", vetoes[0].to_string());
+
+        ws.activate_priority_set("Workday").unwrap();
+        let vetoed: Vec<_> = ws
+            .find_vetoes()
+            .unwrap()
+            .into_iter()
+            .map(|veto| veto.project)
+            .collect();
+        assert_eq!(4, vetoed.len());
+        assert!(!vetoed.contains(&"Project 2".to_string()));
+    }
+
+    #[test]
+    fn test_without_vetoed_keeps_normalization() {
+        let mut ws = build_test_workspace();
+        ws.set_scoring_method(Method::MinMax);
+        ws.set_weight("Project 1", "Fun", 1).unwrap();
+        ws.set_weight("Project 1", "Useful", 2).unwrap();
+        ws.set_weight("Project 2", "Fun", 3).unwrap();
+        ws.set_weight("Project 2", "Useful", 100).unwrap();
+        ws.set_constraint(
+            "Weekend",
+            "Useful",
+            Constraint::new(None, Some(10.0)).unwrap(),
+        )
+        .unwrap();
+
+        let pool = ws.without_vetoed().unwrap();
+        assert!(!pool.get_project_names().contains(&"Project 2".to_string()));
+        let scores = pool.calculate_score().unwrap();
+
+        let mut expected = ws.clone();
+        expected.remove_project("Project 2").unwrap();
+        assert_eq!(expected.calculate_score().unwrap(), scores);
+        assert_eq!(*scores.get("Project 1").unwrap(), 2.0 + 1.0);
+    }
+
+    #[test]
+    fn test_set_constraint_fail() {
+        let mut ws = build_test_workspace();
+        let constraint = Constraint::new(None, Some(3.0)).unwrap();
+
+        assert!(ws.set_constraint("Weekend", "Funn", constraint).is_err());
+        assert!(ws.set_constraint("Holiday", "Fun", constraint).is_err());
+        assert!(ws.remove_constraint("Weekend", "Fun").is_err());
+    }
+
+    #[test]
+    fn test_remove_criterion_with_constraint() {
+        let mut ws = build_test_workspace();
+        let constraint = Constraint::new(None, Some(3.0)).unwrap();
+        ws.set_constraint("Weekend", "Fun", constraint).unwrap();
+
+        let ps = ws.get_priority_set("Weekend").unwrap();
+        assert_eq!(Some(&constraint), ps.get_constraint("Fun"));
+
+        ws.remove_criterion("Fun").unwrap();
+        let ps = ws.get_priority_set("Weekend").unwrap();
+        assert_eq!(None, ps.get_constraint("Fun"));
+    }
+
+    #[test]
+    fn test_budget_criterion() {
+        let mut ws = build_test_workspace();
+        assert_eq!(None, ws.get_budget_criterion());
+        assert!(ws.set_budget_criterion(Some("Cost")).is_err());
+
+        ws.set_budget_criterion(Some("Fun")).unwrap();
+        assert_eq!(Some("Fun"), ws.get_budget_criterion());
+
+        ws.remove_criterion("Fun").unwrap();
+        assert_eq!(None, ws.get_budget_criterion());
+    }
+
+    #[test]
+    fn test_add_dependency() {
+        let mut ws = build_test_workspace();
+        ws.add_dependency("Project 2", "Project 1").unwrap();
+        ws.add_dependency("Project =", "Project 2").unwrap();
+
+        assert_eq!(
+            vec!["Project 1"],
+            ws.get_project("Project 2").unwrap().get_dependencies()
+        );
+        assert_eq!(vec!["Project 2"], ws.get_dependents("Project 1"));
+        assert!(ws.add_dependency("Project 2", "Project 1").is_err());
+        assert!(ws.add_dependency("Project 2", "Project 2").is_err());
+        assert!(ws.add_dependency("Project 2", "Project 3").is_err());
+        assert_eq!(None, ws.find_dependency_cycle());
+    }
+
+    #[test]
+    fn test_add_dependency_cycle() {
+        let mut ws = build_test_workspace();
+        ws.add_dependency("Project 2", "Project 1").unwrap();
+        ws.add_dependency("Project =", "Project 2").unwrap();
+
+        let error = ws.add_dependency("Project 1", "Project =").unwrap_err();
+        assert_eq!(
+            "dependency would create the cycle Project 1 -> Project = -> Project 2 -> Project 1",
+            error.to_string()
+        );
+    }
+
+    #[test]
+    fn test_remove_dependency() {
+        let mut ws = build_test_workspace();
+        ws.add_dependency("Project 2", "Project 1").unwrap();
+        ws.add_dependency("Project =", "Project 1").unwrap();
+
+        ws.remove_dependency("Project 2", "Project 1").unwrap();
+        assert!(ws.remove_dependency("Project 2", "Project 1").is_err());
+
+        ws.remove_project("Project 1").unwrap();
+        assert!(ws
+            .get_project("Project =")
+            .unwrap()
+            .get_dependencies()
+            .is_empty());
+    }
+
+    #[test]
+    fn test_set_status() {
+        let mut ws = build_test_workspace();
+        ws.set_status("Project 1", Status::Active).unwrap();
+        ws.set_status("Project 1", Status::Done).unwrap();
+
+        let project = ws.get_project("Project 1").unwrap();
+        assert_eq!(Status::Done, project.get_status());
+        let history: Vec<_> = project.get_history().iter().map(|c| c.status).collect();
+        assert_eq!(vec![Status::Idea, Status::Active, Status::Done], history);
+
+        assert!(ws.set_status("Project 1", Status::Done).is_err());
+        assert!(ws.set_status("Project 3", Status::Done).is_err());
+    }
+
+    #[test]
+    fn test_archive_project() {
+        let mut ws = build_test_workspace();
+        ws.add_dependency("Project 2", "Project 1").unwrap();
+        ws.archive_project("Project 1").unwrap();
+        assert!(ws.archive_project("Project 1").is_err());
+
+        let pool = ws.without_archived();
+        assert!(!pool.get_project_names().contains(&"Project 1".to_string()));
+        assert!(pool.projects[0].get_dependencies().is_empty());
+        assert_eq!(
+            pool.get_project_names().len() + 1,
+            ws.get_project_names().len()
+        );
+
+        ws.restore_project("Project 1").unwrap();
+        assert!(ws.restore_project("Project 1").is_err());
+        assert_eq!(ws, ws.without_archived());
+    }
+
+    #[test]
+    fn test_without_closed() {
+        let mut ws = build_test_workspace();
+        ws.add_dependency("Project 2", "Project 1").unwrap();
+        ws.set_status("Project 1", Status::Done).unwrap();
+        ws.set_status("Project =", Status::Dropped).unwrap();
+        ws.set_status("Project [toml]", Status::Paused).unwrap();
+
+        let pool = ws.without_closed();
+        assert_eq!(
+            vec!["Project 2", "Project [toml]", "Project\nNewline"],
+            pool.get_project_names()
+        );
+        assert!(pool.projects[0].get_dependencies().is_empty());
+    }
+
+    #[test]
+    fn test_read_unversioned_workspace_from_file() {
+        let temp_file = NamedTempFile::new().unwrap();
+        std::fs::write(
+            temp_file.path(),
+            "projects = []\ncriteria = []\npriority_sets = []\n",
+        )
+        .unwrap();
+
+        let ws = Workspace::read_from_file(temp_file.path().to_path_buf()).unwrap();
+
+        assert_eq!(migration::SCHEMA_VERSION, ws.get_schema_version());
+        assert_eq!(Workspace::new(), ws);
+    }
+
+    #[test]
+    fn test_read_dangling_dependency_from_file() {
+        let temp_file = NamedTempFile::new().unwrap();
+        let mut workspace = build_test_workspace();
+        workspace.add_dependency("Project 2", "Project 1").unwrap();
+        workspace
+            .projects
+            .retain(|project| project.name != "Project 1");
+        workspace
+            .write_to_file(temp_file.path().to_path_buf(), false)
+            .unwrap();
+
+        let error = Workspace::read_from_file(temp_file.path().to_path_buf()).unwrap_err();
+
+        assert_eq!(
+            "project Project 2 depends on unknown project Project 1",
+            error.root_cause().to_string()
+        );
+    }
 }
@@ -9,9 +9,12 @@ mod test_utils;
 
 use cli::build_cli;
 use subcommands::{
-    run_criterion, run_display, run_priority_set, run_project, run_weight, run_workspace,
+    run_analyze, run_criterion, run_display, run_dominance, run_explain, run_history,
+    run_portfolio, run_priority_set, run_project, run_redo, run_snapshot, run_trend, run_undo,
+    run_weight, run_workspace,
 };
 
+#[allow(deprecated)] // human-panic 1.x still refers to `PanicInfo`
 fn main() -> Result<()> {
     setup_panic!(Metadata {
         name: env!("CARGO_PKG_NAME").into(),
@@ -36,6 +39,15 @@ fn run(matches: ArgMatches) -> Result<()> {
         Some(("priority-set", submatches)) => run_priority_set(submatches)?,
         Some(("weight", submatches)) => run_weight(submatches)?,
         Some(("display", submatches)) => run_display(submatches)?,
+        Some(("explain", submatches)) => run_explain(submatches)?,
+        Some(("portfolio", submatches)) => run_portfolio(submatches)?,
+        Some(("dominance", _)) => run_dominance()?,
+        Some(("analyze", submatches)) => run_analyze(submatches)?,
+        Some(("snapshot", submatches)) => run_snapshot(submatches)?,
+        Some(("trend", submatches)) => run_trend(submatches)?,
+        Some(("undo", _)) => run_undo()?,
+        Some(("redo", _)) => run_redo()?,
+        Some(("history", _)) => run_history()?,
         _ => unreachable!("valid command isn't handled"),
     }
 
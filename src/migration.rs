@@ -0,0 +1,165 @@
+//! Upgrades of workspace documents written by older versions of recision.
+//!
+//! Every change to the format of the workspace file increments [`SCHEMA_VERSION`] and adds a
+//! migration that upgrades documents from the previous version. Documents are upgraded one
+//! version at a time before they are parsed, so that every migration only has to know the
+//! format it starts from.
+
+use anyhow::Result;
+use toml::{Table, Value};
+
+use crate::RecicionError;
+
+/// Version of the format of workspace files written by this version of recision.
+pub const SCHEMA_VERSION: u32 = 2;
+
+/// Key of the schema version in workspace documents, which files from before versioning lack.
+const VERSION_KEY: &str = "schema_version";
+
+/// A step that upgrades a workspace document by one schema version.
+pub struct Migration {
+    /// Version of the documents the migration applies to.
+    pub from: u32,
+    /// What the migration changes, as shown when checking for pending migrations.
+    pub description: &'static str,
+    apply: fn(&mut Table) -> Result<()>,
+}
+
+/// Migrations ordered by the version they upgrade from, one for every version below
+/// [`SCHEMA_VERSION`].
+static MIGRATIONS: [Migration; 2] = [
+    Migration {
+        from: 0,
+        description:
+            "record the schema version, the scoring method and the project statuses in the file",
+        apply: fill_in_defaults,
+    },
+    Migration {
+        // older versions would drop the snapshots when writing the file
+        from: 1,
+        description: "allow score snapshots, which older versions of recision can't keep",
+        apply: |_| Ok(()),
+    },
+];
+
+/// Writes out the scoring method and the statuses of the projects, which files from before
+/// versioning leave out when they are the defaults.
+fn fill_in_defaults(document: &mut Table) -> Result<()> {
+    document
+        .entry("scoring_method")
+        .or_insert_with(|| Value::String("weighted-sum".into()));
+
+    if let Some(Value::Array(projects)) = document.get_mut("projects") {
+        for project in projects {
+            let Value::Table(project) = project else {
+                return Err(RecicionError::new(format!("invalid project {}", project)).into());
+            };
+            project
+                .entry("status")
+                .or_insert_with(|| Value::String("idea".into()));
+        }
+    }
+
+    Ok(())
+}
+
+/// Returns the schema version of a workspace document.
+pub fn schema_version(document: &Table) -> Result<u32> {
+    match document.get(VERSION_KEY) {
+        None => Ok(0),
+        Some(Value::Integer(version)) => u32::try_from(*version)
+            .map_err(|_| RecicionError::new(format!("invalid schema version {}", version)).into()),
+        Some(value) => Err(RecicionError::new(format!("invalid schema version {}", value)).into()),
+    }
+}
+
+/// Returns the migrations that upgrade a workspace document to the current schema version, in
+/// the order they apply.
+pub fn pending(document: &Table) -> Result<Vec<&'static Migration>> {
+    let version = schema_version(document)?;
+    if version > SCHEMA_VERSION {
+        return Err(RecicionError::new(format!(
+            "workspace has schema version {}, but this version of recision only supports up to {}",
+            version, SCHEMA_VERSION
+        ))
+        .into());
+    }
+
+    Ok(MIGRATIONS
+        .iter()
+        .filter(|migration| migration.from >= version)
+        .collect())
+}
+
+/// Upgrades a workspace document to the current schema version step by step.
+///
+/// Returns the migrations that were applied, which is empty if the document was up to date.
+pub fn migrate(document: &mut Table) -> Result<Vec<&'static Migration>> {
+    let migrations = pending(document)?;
+    for migration in &migrations {
+        (migration.apply)(document)?;
+        document.insert(
+            VERSION_KEY.into(),
+            Value::Integer((migration.from + 1).into()),
+        );
+    }
+
+    Ok(migrations)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_migrations_cover_every_version() {
+        for (version, migration) in MIGRATIONS.iter().enumerate() {
+            assert_eq!(version as u32, migration.from);
+        }
+        assert_eq!(SCHEMA_VERSION as usize, MIGRATIONS.len());
+    }
+
+    #[test]
+    fn test_migrate_unversioned_document() {
+        let mut document: Table = toml::from_str("projects = []").unwrap();
+        assert_eq!(0, schema_version(&document).unwrap());
+
+        let applied = migrate(&mut document).unwrap();
+
+        assert_eq!(SCHEMA_VERSION as usize, applied.len());
+        assert_eq!(SCHEMA_VERSION, schema_version(&document).unwrap());
+        assert!(migrate(&mut document).unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_migrate_fills_in_defaults() {
+        let mut document: Table = toml::from_str(concat!(
+            "[[projects]]\nname = \"Old\"\n\n",
+            "[[projects]]\nname = \"Done\"\nstatus = \"done\"\n",
+        ))
+        .unwrap();
+
+        migrate(&mut document).unwrap();
+
+        let expected: Table = toml::from_str(concat!(
+            "schema_version = 2\nscoring_method = \"weighted-sum\"\n\n",
+            "[[projects]]\nname = \"Old\"\nstatus = \"idea\"\n\n",
+            "[[projects]]\nname = \"Done\"\nstatus = \"done\"\n",
+        ))
+        .unwrap();
+        assert_eq!(expected, document);
+
+        let mut document: Table = toml::from_str("scoring_method = \"topsis\"").unwrap();
+        migrate(&mut document).unwrap();
+        assert_eq!("topsis", document["scoring_method"].as_str().unwrap());
+    }
+
+    #[test]
+    fn test_migrate_fail() {
+        let mut document: Table = toml::from_str("schema_version = 99").unwrap();
+        assert!(migrate(&mut document).is_err());
+
+        let document: Table = toml::from_str("schema_version = \"one\"").unwrap();
+        assert!(pending(&document).is_err());
+    }
+}
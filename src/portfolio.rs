@@ -0,0 +1,361 @@
+//! Selection of the set of projects with the highest total score that fits into a budget.
+
+use anyhow::Result;
+
+use crate::{RecicionError, Workspace};
+
+/// Tolerance for comparing costs, so that rounding doesn't exclude projects that exactly fit.
+const EPSILON: f64 = 1e-9;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Portfolio {
+    /// Projects in the portfolio, in the order of the workspace.
+    pub projects: Vec<String>,
+    pub score: f64,
+    pub cost: f64,
+}
+
+/// A project that can be selected, with its position in the workspace.
+struct Item {
+    index: usize,
+    score: f64,
+    cost: f64,
+}
+
+/// Finds the portfolio with the highest total score whose total cost fits into the budget,
+/// followed by up to `alternatives` runner-up portfolios.
+///
+/// Costs are the expected weights of the projects on the cost criterion and scores are
+/// calculated with the scoring method of the workspace. Projects vetoed by the active priority
+/// set and projects without a positive score are never selected, and every other project needs
+/// a cost. Only portfolios that can't be
+/// extended by another project within the budget count as runner-ups.
+///
+/// The search is an exact branch and bound that prunes with the fractional relaxation of the
+/// knapsack problem, which stays fast for pools of a few hundred projects.
+pub fn select(
+    workspace: &Workspace,
+    budget: f64,
+    cost_criterion: &str,
+    alternatives: usize,
+) -> Result<Vec<Portfolio>> {
+    if !workspace.criteria.iter().any(|c| c.name == cost_criterion) {
+        return Err(RecicionError::new(format!("no criterion {}", cost_criterion)).into());
+    }
+    if budget < 0.0 {
+        return Err(RecicionError::new(format!("budget {} is negative", budget)).into());
+    }
+
+    let pool = workspace.without_vetoed()?;
+    let unpriced: Vec<_> = pool
+        .projects
+        .iter()
+        .filter(|project| !project.weights.contains_key(cost_criterion))
+        .map(|project| project.name.as_str())
+        .collect();
+    if !unpriced.is_empty() {
+        return Err(RecicionError::new(format!(
+            "no cost on criterion {} for {}",
+            cost_criterion,
+            unpriced.join(", ")
+        ))
+        .into());
+    }
+    let scores = pool.calculate_score()?;
+
+    let mut items = Vec::new();
+    for (index, project) in pool.projects.iter().enumerate() {
+        let cost = project.weights[cost_criterion].expected();
+        if cost < 0.0 {
+            return Err(RecicionError::new(format!(
+                "project {} has a negative cost of {}",
+                project.name, cost
+            ))
+            .into());
+        }
+
+        let score = scores[&project.name];
+        if score > 0.0 {
+            items.push(Item { index, score, cost });
+        }
+    }
+    // the best value for money first, which tightens the bound early
+    items.sort_by(|a, b| (b.score * a.cost).total_cmp(&(a.score * b.cost)));
+
+    let mut search = Search {
+        items: &items,
+        capacity: alternatives + 1,
+        selected: vec![false; items.len()],
+        best: Vec::new(),
+    };
+    search.run(0, 0.0, budget);
+
+    let names = pool.get_project_names();
+    Ok(search
+        .best
+        .into_iter()
+        .map(|(score, cost, selected)| {
+            let mut indices: Vec<_> = items
+                .iter()
+                .zip(selected)
+                .filter(|(_, selected)| *selected)
+                .map(|(item, _)| item.index)
+                .collect();
+            indices.sort();
+            Portfolio {
+                projects: indices.into_iter().map(|i| names[i].clone()).collect(),
+                score,
+                cost,
+            }
+        })
+        .collect())
+}
+
+struct Search<'a> {
+    /// Items sorted by decreasing ratio of score to cost.
+    items: &'a [Item],
+    /// Number of portfolios to keep.
+    capacity: usize,
+    selected: Vec<bool>,
+    /// Best portfolios found so far as score, cost and selection, from the best to the worst.
+    best: Vec<(f64, f64, Vec<bool>)>,
+}
+
+impl Search<'_> {
+    fn run(&mut self, next: usize, score: f64, remaining: f64) {
+        if next == self.items.len() {
+            self.record(score, remaining);
+            return;
+        }
+        if self.best.len() == self.capacity
+            && self.bound(next, score, remaining) <= self.best[self.capacity - 1].0
+        {
+            return;
+        }
+
+        let item = &self.items[next];
+        if item.cost <= remaining + EPSILON {
+            self.selected[next] = true;
+            self.run(next + 1, score + item.score, remaining - item.cost);
+            self.selected[next] = false;
+        }
+        self.run(next + 1, score, remaining);
+    }
+
+    /// Upper bound for the score reachable from a partial selection, filling the remaining
+    /// budget with fractions of the next items. Items that cost no more than the tolerance are
+    /// free, so only items with a real cost are split.
+    fn bound(&self, next: usize, mut score: f64, remaining: f64) -> f64 {
+        // items taken within the tolerance can overdraw the budget by a rounding error
+        let mut remaining = remaining.max(0.0);
+        for item in &self.items[next..] {
+            if item.cost <= remaining + EPSILON {
+                remaining = (remaining - item.cost).max(0.0);
+                score += item.score;
+            } else {
+                return score + item.score * remaining / item.cost;
+            }
+        }
+        score
+    }
+
+    fn record(&mut self, score: f64, remaining: f64) {
+        let extensible = self
+            .items
+            .iter()
+            .zip(&self.selected)
+            .any(|(item, selected)| !selected && item.cost <= remaining + EPSILON);
+        if extensible {
+            return;
+        }
+
+        let cost = self
+            .items
+            .iter()
+            .zip(&self.selected)
+            .filter(|(_, selected)| **selected)
+            .map(|(item, _)| item.cost)
+            .sum();
+        let position = self
+            .best
+            .iter()
+            .position(|(best, _, _)| score > *best)
+            .unwrap_or(self.best.len());
+        self.best
+            .insert(position, (score, cost, self.selected.clone()));
+        self.best.truncate(self.capacity);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Constraint, Criterion, Project};
+
+    fn build_test_workspace(projects: &[(f64, f64)]) -> Workspace {
+        let mut workspace = Workspace::new();
+        workspace
+            .add_criterion(Criterion::new("Value"))
+            .unwrap()
+            .add_criterion(Criterion::new("Effort"))
+            .unwrap();
+        workspace.add_priority_set("Workday").unwrap();
+        workspace.activate_priority_set("Workday").unwrap();
+        workspace.set_priority("Effort", 0.0).unwrap();
+
+        for (i, (value, effort)) in projects.iter().enumerate() {
+            let name = format!("Project {}", i + 1);
+            workspace.add_project(Project::new(&name)).unwrap();
+            workspace.set_weight(&name, "Value", *value).unwrap();
+            workspace.set_weight(&name, "Effort", *effort).unwrap();
+        }
+
+        workspace
+    }
+
+    #[test]
+    fn test_select() {
+        let ws = build_test_workspace(&[(6.0, 5.0), (5.0, 4.0), (4.0, 3.0), (3.0, 2.0)]);
+        let portfolios = select(&ws, 9.0, "Effort", 2).unwrap();
+
+        assert_eq!(3, portfolios.len());
+        assert_eq!(
+            vec!["Project 2", "Project 3", "Project 4"],
+            portfolios[0].projects
+        );
+        assert_eq!(12.0, portfolios[0].score);
+        assert_eq!(9.0, portfolios[0].cost);
+        assert_eq!(vec!["Project 1", "Project 2"], portfolios[1].projects);
+        assert_eq!(11.0, portfolios[1].score);
+        // runner-ups can't be extended within the budget
+        assert_eq!(vec!["Project 1", "Project 3"], portfolios[2].projects);
+    }
+
+    #[test]
+    fn test_select_matches_exhaustive_search() {
+        let mut state = 17u64;
+        let mut random = |max: u64| {
+            state = state.wrapping_mul(6364136223846793005).wrapping_add(1);
+            ((state >> 33) % max) as f64 + 1.0
+        };
+
+        for _ in 0..20 {
+            let projects: Vec<_> = (0..12).map(|_| (random(20), random(10))).collect();
+            let budget = random(40);
+            let ws = build_test_workspace(&projects);
+
+            let best = (0..1u32 << projects.len())
+                .filter_map(|mask| {
+                    let chosen = projects
+                        .iter()
+                        .enumerate()
+                        .filter(|(i, _)| mask & (1 << i) != 0);
+                    let cost: f64 = chosen.clone().map(|(_, (_, effort))| effort).sum();
+                    let score: f64 = chosen.map(|(_, (value, _))| value).sum();
+                    (cost <= budget).then_some(score)
+                })
+                .fold(0.0, f64::max);
+
+            let portfolios = select(&ws, budget, "Effort", 0).unwrap();
+            assert_eq!(best, portfolios[0].score);
+            assert!(portfolios[0].cost <= budget);
+        }
+    }
+
+    #[test]
+    fn test_select_from_large_pool() {
+        let projects: Vec<_> = (0..300)
+            .map(|i| ((i * 37 % 101) as f64 + 1.0, (i * 53 % 19) as f64 + 1.0))
+            .collect();
+        let ws = build_test_workspace(&projects);
+
+        let portfolios = select(&ws, 250.0, "Effort", 3).unwrap();
+
+        assert_eq!(4, portfolios.len());
+        assert!(portfolios.iter().all(|p| p.cost <= 250.0));
+        assert!(portfolios.windows(2).all(|p| p[0].score >= p[1].score));
+    }
+
+    #[test]
+    fn test_select_skips_vetoed_projects() {
+        let mut ws = build_test_workspace(&[(6.0, 5.0), (5.0, 4.0)]);
+        ws.set_constraint(
+            "Workday",
+            "Value",
+            Constraint::new(None, Some(5.0)).unwrap(),
+        )
+        .unwrap();
+
+        let portfolios = select(&ws, 5.0, "Effort", 0).unwrap();
+
+        assert_eq!(vec!["Project 2"], portfolios[0].projects);
+    }
+
+    #[test]
+    fn test_select_fail() {
+        let ws = build_test_workspace(&[(6.0, 5.0)]);
+
+        assert!(select(&ws, 5.0, "Cost", 0).is_err());
+        assert!(select(&ws, -1.0, "Effort", 0).is_err());
+
+        let mut ws = ws;
+        ws.set_weight("Project 1", "Effort", -1).unwrap();
+        assert!(select(&ws, 5.0, "Effort", 0).is_err());
+    }
+
+    #[test]
+    fn test_select_free_project_with_exhausted_budget() {
+        let ws = build_test_workspace(&[(6.0, 0.1), (5.0, 0.2), (4.0, 0.0)]);
+        let portfolios = select(&ws, 0.3, "Effort", 0).unwrap();
+
+        assert_eq!(
+            vec!["Project 1", "Project 2", "Project 3"],
+            portfolios[0].projects
+        );
+        assert_eq!(15.0, portfolios[0].score);
+
+        let items = [
+            Item {
+                index: 0,
+                score: 6.0,
+                cost: 0.3,
+            },
+            Item {
+                index: 1,
+                score: 4.0,
+                cost: 0.0,
+            },
+        ];
+        let search = Search {
+            items: &items,
+            capacity: 1,
+            selected: vec![false; 2],
+            best: Vec::new(),
+        };
+        // 0.3 - 0.1 - 0.2 leaves a budget just below zero
+        assert_eq!(10.0, search.bound(1, 6.0, 0.3 - 0.1 - 0.2));
+    }
+
+    #[test]
+    fn test_select_without_cost() {
+        let mut ws = build_test_workspace(&[(6.0, 8.0)]);
+        ws.add_project(Project::new("Unpriced")).unwrap();
+        ws.set_weight("Unpriced", "Value", 5).unwrap();
+
+        let error = select(&ws, 5.0, "Effort", 0).unwrap_err();
+
+        assert_eq!(
+            "no cost on criterion Effort for Unpriced",
+            error.to_string()
+        );
+    }
+
+    #[test]
+    fn test_select_nothing_fits() {
+        let ws = build_test_workspace(&[(6.0, 5.0)]);
+        let portfolios = select(&ws, 4.0, "Effort", 2).unwrap();
+
+        assert_eq!(1, portfolios.len());
+        assert!(portfolios[0].projects.is_empty());
+    }
+}
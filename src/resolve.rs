@@ -0,0 +1,179 @@
+//! Resolution of user supplied references to projects, criteria and priority sets.
+//!
+//! A reference is resolved to the index of an item by trying, in this order,
+//!
+//! 1. an exact match of the name,
+//! 2. the 1-based ID as printed by the `list` subcommands,
+//! 3. a case-insensitive match of the whole name,
+//! 4. a unique case-insensitive prefix of the name.
+
+use std::fmt::{self, Display, Formatter};
+
+/// The kinds of items that can be referenced by name or ID.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ItemKind {
+    Project,
+    Criterion,
+    PrioritySet,
+}
+
+impl Display for ItemKind {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            ItemKind::Project => write!(f, "project"),
+            ItemKind::Criterion => write!(f, "criterion"),
+            ItemKind::PrioritySet => write!(f, "priority set"),
+        }
+    }
+}
+
+#[derive(Debug, PartialEq)]
+pub enum ResolveError {
+    NotFound {
+        kind: ItemKind,
+        reference: String,
+    },
+    Ambiguous {
+        kind: ItemKind,
+        reference: String,
+        candidates: Vec<String>,
+    },
+}
+
+impl Display for ResolveError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            ResolveError::NotFound { kind, reference } => write!(f, "no {} {}", kind, reference),
+            ResolveError::Ambiguous {
+                kind,
+                reference,
+                candidates,
+            } => write!(
+                f,
+                "{} reference '{}' is ambiguous, candidates are: {}",
+                kind,
+                reference,
+                candidates
+                    .iter()
+                    .map(|candidate| format!("'{}'", candidate))
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            ),
+        }
+    }
+}
+
+impl std::error::Error for ResolveError {}
+
+/// Resolves `reference` to the index of one of `names`.
+pub fn resolve<S: AsRef<str>>(
+    names: &[S],
+    reference: &str,
+    kind: ItemKind,
+) -> Result<usize, ResolveError> {
+    if let Some(index) = names.iter().position(|name| name.as_ref() == reference) {
+        return Ok(index);
+    }
+
+    if let Some(index) = reference
+        .parse::<usize>()
+        .ok()
+        .and_then(|id| id.checked_sub(1))
+        .filter(|index| *index < names.len())
+    {
+        return Ok(index);
+    }
+
+    let lowercase_reference = reference.to_lowercase();
+    let matching = |predicate: &dyn Fn(&str) -> bool| -> Vec<usize> {
+        names
+            .iter()
+            .enumerate()
+            .filter(|(_, name)| predicate(&name.as_ref().to_lowercase()))
+            .map(|(index, _)| index)
+            .collect()
+    };
+
+    let mut candidates = matching(&|name| name == lowercase_reference);
+    if candidates.is_empty() && !reference.is_empty() {
+        candidates = matching(&|name| name.starts_with(&lowercase_reference));
+    }
+
+    match candidates.as_slice() {
+        [] => Err(ResolveError::NotFound {
+            kind,
+            reference: reference.into(),
+        }),
+        [index] => Ok(*index),
+        _ => Err(ResolveError::Ambiguous {
+            kind,
+            reference: reference.into(),
+            candidates: candidates
+                .iter()
+                .map(|index| names[*index].as_ref().to_string())
+                .collect(),
+        }),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const NAMES: [&str; 5] = ["Fun", "Funding", "Useful", "useful", "2"];
+
+    #[test]
+    fn test_resolve_exact_name() {
+        assert_eq!(Ok(2), resolve(&NAMES, "Useful", ItemKind::Criterion));
+        assert_eq!(Ok(3), resolve(&NAMES, "useful", ItemKind::Criterion));
+    }
+
+    #[test]
+    fn test_exact_name_takes_precedence_over_id() {
+        assert_eq!(Ok(4), resolve(&NAMES, "2", ItemKind::Criterion));
+    }
+
+    #[test]
+    fn test_resolve_id() {
+        assert_eq!(Ok(0), resolve(&NAMES, "1", ItemKind::Criterion));
+        assert_eq!(Ok(2), resolve(&NAMES, "3", ItemKind::Criterion));
+    }
+
+    #[test]
+    fn test_resolve_case_insensitive() {
+        assert_eq!(Ok(0), resolve(&NAMES, "fun", ItemKind::Criterion));
+        assert_eq!(Ok(1), resolve(&NAMES, "fund", ItemKind::Criterion));
+    }
+
+    #[test]
+    fn test_resolve_ambiguous() {
+        let result = resolve(&NAMES, "USEFUL", ItemKind::Criterion);
+        assert_eq!(
+            Err(ResolveError::Ambiguous {
+                kind: ItemKind::Criterion,
+                reference: "USEFUL".into(),
+                candidates: vec!["Useful".into(), "useful".into()]
+            }),
+            result
+        );
+
+        let message = resolve(&NAMES, "u", ItemKind::Criterion)
+            .unwrap_err()
+            .to_string();
+        assert!(message.contains("'Useful', 'useful'"));
+    }
+
+    #[test]
+    fn test_resolve_not_found() {
+        assert_eq!(
+            Err(ResolveError::NotFound {
+                kind: ItemKind::Project,
+                reference: "6".into()
+            }),
+            resolve(&NAMES, "6", ItemKind::Project)
+        );
+        assert!(resolve(&NAMES, "Cheap", ItemKind::Project).is_err());
+        assert!(resolve(&NAMES, "", ItemKind::Project).is_err());
+        assert!(resolve::<&str>(&[], "1", ItemKind::Project).is_err());
+    }
+}
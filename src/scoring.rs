@@ -0,0 +1,440 @@
+//! Methods to turn the weights of the projects into scores.
+//!
+//! All methods operate on the whole grid at once, because the normalizing methods need to know
+//! the distribution of the weights of every criterion across all projects.
+
+use std::{
+    fmt::{self, Display, Formatter},
+    str::FromStr,
+};
+
+use anyhow::Result;
+use serde::{Deserialize, Serialize};
+
+use crate::RecicionError;
+
+pub trait ScoringMethod {
+    /// Calculates the score of every project.
+    ///
+    /// `weights` holds one row per project with one weight per criterion, `priorities` holds the
+    /// priority of every criterion.
+    fn score(&self, weights: &[Vec<f64>], priorities: &[f64]) -> Result<Vec<f64>>;
+
+    /// Whether the method is only defined for non-negative weights.
+    fn requires_non_negative_weights(&self) -> bool {
+        false
+    }
+
+    /// Splits the scores into the contributions of the criteria, `contributions[project]` holding
+    /// one contribution per criterion, if every score is the sum of its contributions.
+    fn contributions(&self, _weights: &[Vec<f64>], _priorities: &[f64]) -> Option<Vec<Vec<f64>>> {
+        None
+    }
+}
+
+/// Scores of a project when its uncertain weights take their least favorable, expected and most
+/// favorable values.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ScoreRange {
+    pub worst: f64,
+    pub expected: f64,
+    pub best: f64,
+}
+
+/// Sum of the weights multiplied by the priorities of their criteria.
+pub struct WeightedSum;
+
+impl ScoringMethod for WeightedSum {
+    fn score(&self, weights: &[Vec<f64>], priorities: &[f64]) -> Result<Vec<f64>> {
+        Ok(weights
+            .iter()
+            .map(|row| row.iter().zip(priorities).map(|(w, p)| w * p).sum())
+            .collect())
+    }
+
+    fn contributions(&self, weights: &[Vec<f64>], priorities: &[f64]) -> Option<Vec<Vec<f64>>> {
+        Some(
+            weights
+                .iter()
+                .map(|row| row.iter().zip(priorities).map(|(w, p)| w * p).collect())
+                .collect(),
+        )
+    }
+}
+
+/// Product of the weights raised to the priorities of their criteria.
+///
+/// The weighted product is only defined for non-negative weights.
+pub struct WeightedProduct;
+
+impl ScoringMethod for WeightedProduct {
+    fn score(&self, weights: &[Vec<f64>], priorities: &[f64]) -> Result<Vec<f64>> {
+        if weights.iter().flatten().any(|w| *w < 0.0) {
+            return Err(RecicionError::new(
+                "the weighted product requires non-negative weights".into(),
+            )
+            .into());
+        }
+
+        Ok(weights
+            .iter()
+            .map(|row| {
+                row.iter()
+                    .zip(priorities)
+                    .map(|(w, p)| w.powf(*p))
+                    .product()
+            })
+            .collect())
+    }
+
+    fn requires_non_negative_weights(&self) -> bool {
+        true
+    }
+}
+
+/// Weighted sum of the weights after scaling every criterion to the range from 0 to 1.
+///
+/// Criteria on which all projects have the same weight don't contribute to the score.
+pub struct MinMaxNormalized;
+
+impl MinMaxNormalized {
+    fn normalize(weights: &[Vec<f64>]) -> Vec<Vec<f64>> {
+        normalize_columns(weights, |column| {
+            let min = column.iter().cloned().fold(f64::INFINITY, f64::min);
+            let max = column.iter().cloned().fold(f64::NEG_INFINITY, f64::max);
+            (min, max - min)
+        })
+    }
+}
+
+impl ScoringMethod for MinMaxNormalized {
+    fn score(&self, weights: &[Vec<f64>], priorities: &[f64]) -> Result<Vec<f64>> {
+        WeightedSum.score(&Self::normalize(weights), priorities)
+    }
+
+    fn contributions(&self, weights: &[Vec<f64>], priorities: &[f64]) -> Option<Vec<Vec<f64>>> {
+        WeightedSum.contributions(&Self::normalize(weights), priorities)
+    }
+}
+
+/// Weighted sum of the standard scores of the weights of every criterion.
+///
+/// Criteria on which all projects have the same weight don't contribute to the score.
+pub struct ZScoreNormalized;
+
+impl ZScoreNormalized {
+    fn normalize(weights: &[Vec<f64>]) -> Vec<Vec<f64>> {
+        normalize_columns(weights, |column| {
+            let n = column.len() as f64;
+            let mean = column.iter().sum::<f64>() / n;
+            let variance = column.iter().map(|w| (w - mean).powi(2)).sum::<f64>() / n;
+            (mean, variance.sqrt())
+        })
+    }
+}
+
+impl ScoringMethod for ZScoreNormalized {
+    fn score(&self, weights: &[Vec<f64>], priorities: &[f64]) -> Result<Vec<f64>> {
+        WeightedSum.score(&Self::normalize(weights), priorities)
+    }
+
+    fn contributions(&self, weights: &[Vec<f64>], priorities: &[f64]) -> Option<Vec<Vec<f64>>> {
+        WeightedSum.contributions(&Self::normalize(weights), priorities)
+    }
+}
+
+/// Result of the TOPSIS evaluation of a single project.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct TopsisResult {
+    /// Euclidean distance to the ideal project, which has the best weight on every criterion.
+    pub ideal_distance: f64,
+    /// Euclidean distance to the anti-ideal project, which has the worst weight on every
+    /// criterion.
+    pub anti_ideal_distance: f64,
+    /// Relative closeness to the ideal project, between 0 and 1.
+    pub closeness: f64,
+}
+
+/// Technique for Order Preference by Similarity to Ideal Solution.
+///
+/// The weights of every criterion are normalized by their Euclidean norm and multiplied by the
+/// priorities, which are scaled to sum up to one. Projects are ranked by their closeness
+/// coefficient `D- / (D+ + D-)`, where `D+` and `D-` are the distances to the ideal and
+/// anti-ideal projects.
+pub struct Topsis;
+
+impl Topsis {
+    pub fn evaluate(&self, weights: &[Vec<f64>], priorities: &[f64]) -> Vec<TopsisResult> {
+        let priority_sum: f64 = priorities.iter().map(|p| p.abs()).sum();
+        let priorities: Vec<_> = priorities
+            .iter()
+            .map(|p| {
+                if priority_sum == 0.0 {
+                    *p
+                } else {
+                    p / priority_sum
+                }
+            })
+            .collect();
+
+        let normalized = normalize_columns(weights, |column| {
+            (0.0, column.iter().map(|w| w * w).sum::<f64>().sqrt())
+        });
+        let weighted: Vec<Vec<f64>> = normalized
+            .iter()
+            .map(|row| row.iter().zip(&priorities).map(|(r, p)| r * p).collect())
+            .collect();
+
+        let criteria_count = priorities.len();
+        let column = |criterion: usize| weighted.iter().map(move |row| row[criterion]);
+        let ideal: Vec<_> = (0..criteria_count)
+            .map(|c| column(c).fold(f64::NEG_INFINITY, f64::max))
+            .collect();
+        let anti_ideal: Vec<_> = (0..criteria_count)
+            .map(|c| column(c).fold(f64::INFINITY, f64::min))
+            .collect();
+
+        let distance = |row: &[f64], reference: &[f64]| {
+            row.iter()
+                .zip(reference)
+                .map(|(v, r)| (v - r).powi(2))
+                .sum::<f64>()
+                .sqrt()
+        };
+
+        weighted
+            .iter()
+            .map(|row| {
+                let ideal_distance = distance(row, &ideal);
+                let anti_ideal_distance = distance(row, &anti_ideal);
+                let total_distance = ideal_distance + anti_ideal_distance;
+                TopsisResult {
+                    ideal_distance,
+                    anti_ideal_distance,
+                    closeness: if total_distance == 0.0 {
+                        0.0
+                    } else {
+                        anti_ideal_distance / total_distance
+                    },
+                }
+            })
+            .collect()
+    }
+}
+
+impl ScoringMethod for Topsis {
+    fn score(&self, weights: &[Vec<f64>], priorities: &[f64]) -> Result<Vec<f64>> {
+        Ok(self
+            .evaluate(weights, priorities)
+            .iter()
+            .map(|result| result.closeness)
+            .collect())
+    }
+}
+
+/// Replaces every weight `w` by `(w - offset) / scale`, where offset and scale are calculated
+/// per criterion. Criteria with a scale of zero are mapped to zero.
+fn normalize_columns(
+    weights: &[Vec<f64>],
+    offset_and_scale: impl Fn(&[f64]) -> (f64, f64),
+) -> Vec<Vec<f64>> {
+    let criteria_count = weights.first().map_or(0, Vec::len);
+    let parameters: Vec<_> = (0..criteria_count)
+        .map(|criterion| {
+            let column: Vec<_> = weights.iter().map(|row| row[criterion]).collect();
+            offset_and_scale(&column)
+        })
+        .collect();
+
+    weights
+        .iter()
+        .map(|row| {
+            row.iter()
+                .zip(&parameters)
+                .map(|(w, (offset, scale))| {
+                    if *scale == 0.0 {
+                        0.0
+                    } else {
+                        (w - offset) / scale
+                    }
+                })
+                .collect()
+        })
+        .collect()
+}
+
+/// The built-in scoring methods, as they are selected in the workspace file and on the command
+/// line.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum Method {
+    #[default]
+    WeightedSum,
+    WeightedProduct,
+    MinMax,
+    ZScore,
+    Topsis,
+}
+
+impl Method {
+    pub const ALL: [Method; 5] = [
+        Method::WeightedSum,
+        Method::WeightedProduct,
+        Method::MinMax,
+        Method::ZScore,
+        Method::Topsis,
+    ];
+
+    pub fn scoring_method(&self) -> &'static dyn ScoringMethod {
+        match self {
+            Method::WeightedSum => &WeightedSum,
+            Method::WeightedProduct => &WeightedProduct,
+            Method::MinMax => &MinMaxNormalized,
+            Method::ZScore => &ZScoreNormalized,
+            Method::Topsis => &Topsis,
+        }
+    }
+}
+
+impl Display for Method {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            Method::WeightedSum => write!(f, "weighted-sum"),
+            Method::WeightedProduct => write!(f, "weighted-product"),
+            Method::MinMax => write!(f, "min-max"),
+            Method::ZScore => write!(f, "z-score"),
+            Method::Topsis => write!(f, "topsis"),
+        }
+    }
+}
+
+impl FromStr for Method {
+    type Err = RecicionError;
+
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        Method::ALL
+            .into_iter()
+            .find(|method| method.to_string() == s)
+            .ok_or(RecicionError::new(format!("no scoring method {}", s)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn grid() -> Vec<Vec<f64>> {
+        vec![vec![1.0, 10.0], vec![3.0, 20.0], vec![2.0, 60.0]]
+    }
+
+    fn assert_close(expected: &[f64], actual: &[f64]) {
+        assert_eq!(expected.len(), actual.len());
+        for (e, a) in expected.iter().zip(actual) {
+            assert!(
+                (e - a).abs() < 1e-9,
+                "expected {expected:?}, got {actual:?}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_weighted_sum() {
+        let scores = WeightedSum.score(&grid(), &[2.0, 1.0]).unwrap();
+        assert_close(&[12.0, 26.0, 64.0], &scores);
+    }
+
+    #[test]
+    fn test_weighted_product() {
+        let scores = WeightedProduct.score(&grid(), &[2.0, 0.5]).unwrap();
+        assert_close(
+            &[10f64.sqrt(), 9.0 * 20f64.sqrt(), 4.0 * 60f64.sqrt()],
+            &scores,
+        );
+
+        let result = WeightedProduct.score(&[vec![-1.0]], &[1.0]);
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_min_max_normalized() {
+        let scores = MinMaxNormalized.score(&grid(), &[1.0, 1.0]).unwrap();
+        assert_close(&[0.0, 1.2, 1.5], &scores);
+    }
+
+    #[test]
+    fn test_z_score_normalized() {
+        let scores = ZScoreNormalized.score(&grid(), &[1.0, 0.0]).unwrap();
+        let std = (2.0f64 / 3.0).sqrt();
+        assert_close(&[-1.0 / std, 1.0 / std, 0.0], &scores);
+    }
+
+    #[test]
+    fn test_constant_criterion_is_ignored() {
+        let weights = vec![vec![1.0, 5.0], vec![2.0, 5.0]];
+        for method in [Method::MinMax, Method::ZScore] {
+            let scores = method
+                .scoring_method()
+                .score(&weights, &[1.0, 100.0])
+                .unwrap();
+            assert!(scores.iter().all(|score| score.is_finite()));
+            assert!(scores[0] < scores[1]);
+        }
+    }
+
+    #[test]
+    fn test_topsis() {
+        let weights = vec![vec![3.0, 4.0], vec![0.0, 0.0], vec![3.0, 0.0]];
+        let results = Topsis.evaluate(&weights, &[1.0, 1.0]);
+
+        // the normalized columns [0.5^0.5, 0, 0.5^0.5] and [1, 0, 0] are halved by the
+        // priorities, so the ideal project is [v, 0.5] and the anti-ideal project is [0, 0]
+        let v = 0.5f64.sqrt() / 2.0;
+        let diagonal = (v * v + 0.25).sqrt();
+        let ideal_distances: Vec<_> = results.iter().map(|r| r.ideal_distance).collect();
+        let anti_ideal_distances: Vec<_> = results.iter().map(|r| r.anti_ideal_distance).collect();
+        let closeness: Vec<_> = results.iter().map(|r| r.closeness).collect();
+
+        assert_close(&[0.0, diagonal, 0.5], &ideal_distances);
+        assert_close(&[diagonal, 0.0, v], &anti_ideal_distances);
+        assert_close(&[1.0, 0.0, v / (v + 0.5)], &closeness);
+    }
+
+    #[test]
+    fn test_topsis_is_invariant_to_priority_scale() {
+        let a = Topsis.score(&grid(), &[1.0, 2.0]).unwrap();
+        let b = Topsis.score(&grid(), &[10.0, 20.0]).unwrap();
+        assert_close(&a, &b);
+    }
+
+    #[test]
+    fn test_topsis_identical_projects() {
+        let results = Topsis.evaluate(&[vec![1.0], vec![1.0]], &[1.0]);
+        assert!(results.iter().all(|result| result.closeness == 0.0));
+    }
+
+    #[test]
+    fn test_contributions_sum_up_to_scores() {
+        let priorities = [2.0, 0.5];
+        for method in Method::ALL {
+            let method = method.scoring_method();
+            let Some(contributions) = method.contributions(&grid(), &priorities) else {
+                continue;
+            };
+            let sums: Vec<f64> = contributions.iter().map(|row| row.iter().sum()).collect();
+            assert_close(&method.score(&grid(), &priorities).unwrap(), &sums);
+        }
+
+        assert!(WeightedProduct
+            .contributions(&grid(), &priorities)
+            .is_none());
+        assert!(Topsis.contributions(&grid(), &priorities).is_none());
+    }
+
+    #[test]
+    fn test_method_from_str() {
+        for method in Method::ALL {
+            assert_eq!(method, method.to_string().parse().unwrap());
+        }
+        assert!("weighted".parse::<Method>().is_err());
+    }
+}
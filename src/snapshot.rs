@@ -0,0 +1,262 @@
+//! Scores recorded over time, to follow how the ranking of the projects shifts.
+
+use std::collections::HashMap;
+
+use anyhow::Result;
+use serde::{Deserialize, Serialize};
+use toml::value::Datetime;
+
+use crate::{analysis, scoring::Method, status, RecicionError, Scenario, Workspace};
+
+/// Scores of the projects in the pool under every priority set at one point in time.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Snapshot {
+    label: String,
+    at: Datetime,
+    /// Scoring method the scores were calculated with.
+    method: Method,
+    /// Scores of the projects by priority set and project.
+    scores: HashMap<String, HashMap<String, f64>>,
+}
+
+impl Snapshot {
+    /// Scores the projects that aren't archived, done or dropped under every priority set of the
+    /// workspace. Projects vetoed by a priority set have no score under it.
+    pub(crate) fn take(workspace: &Workspace, label: &str) -> Result<Self> {
+        let pool = workspace.without_archived().without_closed();
+        let method = pool.get_scoring_method();
+        pool.check_scoring_method(method.scoring_method())?;
+
+        let mut scores = HashMap::new();
+        for priority_set in &pool.priority_sets {
+            // vetoed projects have no score under the set
+            let set_pool = pool.without_vetoed_for(priority_set);
+            let (weights, priorities) =
+                set_pool.scoring_input_for(priority_set, Scenario::Expected);
+            let set_scores = method.scoring_method().score(&weights, &priorities)?;
+            scores.insert(
+                priority_set.name.clone(),
+                set_pool
+                    .get_project_names()
+                    .into_iter()
+                    .zip(set_scores)
+                    .collect(),
+            );
+        }
+
+        Ok(Self {
+            label: label.into(),
+            at: status::now(),
+            method,
+            scores,
+        })
+    }
+
+    pub fn get_label(&self) -> &str {
+        &self.label
+    }
+
+    pub fn get_at(&self) -> &Datetime {
+        &self.at
+    }
+
+    pub fn get_method(&self) -> Method {
+        self.method
+    }
+
+    /// Returns the scores of the projects under a priority set, if the set existed when the
+    /// snapshot was taken.
+    pub fn get_scores(&self, priority_set: &str) -> Option<&HashMap<String, f64>> {
+        self.scores.get(priority_set)
+    }
+}
+
+/// Scores and ranks of the projects across the snapshots that include a priority set.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Trend {
+    /// Labels of the snapshots, from the oldest to the newest.
+    pub snapshots: Vec<String>,
+    /// Projects in any of the snapshots, ordered by their latest rank.
+    pub projects: Vec<String>,
+    /// Score of each project in each snapshot, if the project was in the pool.
+    pub scores: Vec<Vec<Option<f64>>>,
+    /// Rank of each project in each snapshot, starting at 1 for the highest score.
+    pub ranks: Vec<Vec<Option<usize>>>,
+}
+
+impl Trend {
+    /// Returns how many places a project moved up from the first to the last snapshot that
+    /// include it, which is negative if it moved down.
+    pub fn rank_change(&self, project: usize) -> Option<i64> {
+        let mut ranks = self.ranks[project].iter().flatten();
+        let first = *ranks.next()?;
+        let last = *ranks.last()?;
+        Some(first as i64 - last as i64)
+    }
+
+    /// Returns up to `count` projects that changed their rank the most, the biggest movers first.
+    pub fn movers(&self, count: usize) -> Vec<usize> {
+        let mut movers: Vec<_> = (0..self.projects.len())
+            .filter(|project| self.rank_change(*project).is_some_and(|change| change != 0))
+            .collect();
+        movers.sort_by_key(|project| {
+            std::cmp::Reverse(self.rank_change(*project).unwrap_or_default().abs())
+        });
+        movers.truncate(count);
+        movers
+    }
+}
+
+/// Follows the scores and ranks of the projects under a priority set across the snapshots.
+pub fn trend(workspace: &Workspace, priority_set: &str) -> Result<Trend> {
+    if workspace.get_priority_set(priority_set).is_none() {
+        return Err(RecicionError::new(format!("no priority set {}", priority_set)).into());
+    }
+
+    let snapshots: Vec<_> = workspace
+        .snapshots
+        .iter()
+        .filter_map(|snapshot| Some((snapshot, snapshot.get_scores(priority_set)?)))
+        .collect();
+
+    // projects of the workspace keep their order, projects removed since come after them
+    let mut projects = workspace.get_project_names();
+    for (_, scores) in &snapshots {
+        let mut removed: Vec<_> = scores
+            .keys()
+            .filter(|name| !projects.contains(name))
+            .cloned()
+            .collect();
+        removed.sort();
+        projects.extend(removed);
+    }
+    projects.retain(|name| {
+        snapshots
+            .iter()
+            .any(|(_, scores)| scores.contains_key(name))
+    });
+
+    let mut scores = vec![Vec::with_capacity(snapshots.len()); projects.len()];
+    let mut ranks = vec![Vec::with_capacity(snapshots.len()); projects.len()];
+    for (_, snapshot_scores) in &snapshots {
+        let included: Vec<_> = (0..projects.len())
+            .filter(|project| snapshot_scores.contains_key(&projects[*project]))
+            .collect();
+        let included_scores: Vec<_> = included
+            .iter()
+            .map(|project| snapshot_scores[&projects[*project]])
+            .collect();
+
+        for project in 0..projects.len() {
+            scores[project].push(snapshot_scores.get(&projects[project]).copied());
+            ranks[project].push(None);
+        }
+        // projects with equal scores share a rank, so they don't show up as movers
+        let included_ranks = analysis::ranks_with_ties(&included_scores);
+        for (index, rank) in included.into_iter().zip(included_ranks) {
+            *ranks[index].last_mut().expect("rank was pushed") = Some(rank);
+        }
+    }
+
+    // order by the latest rank, projects missing from the latest snapshots last
+    let latest_rank = |project: usize| {
+        ranks[project]
+            .iter()
+            .enumerate()
+            .rev()
+            .find_map(|(snapshot, rank)| rank.map(|rank| (usize::MAX - snapshot, rank)))
+    };
+    let mut order: Vec<_> = (0..projects.len()).collect();
+    order.sort_by_key(|project| latest_rank(*project));
+
+    Ok(Trend {
+        snapshots: snapshots
+            .iter()
+            .map(|(snapshot, _)| snapshot.label.clone())
+            .collect(),
+        projects: order.iter().map(|i| projects[*i].clone()).collect(),
+        scores: order.iter().map(|i| scores[*i].clone()).collect(),
+        ranks: order.iter().map(|i| ranks[*i].clone()).collect(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Criterion, Project};
+
+    fn build_test_workspace() -> Workspace {
+        let mut workspace = Workspace::new();
+        for name in ["Alpha", "Beta", "Gamma"] {
+            workspace.add_project(Project::new(name)).unwrap();
+        }
+        workspace.add_criterion(Criterion::new("Fun")).unwrap();
+        workspace.add_priority_set("Workday").unwrap();
+        workspace.activate_priority_set("Workday").unwrap();
+
+        workspace.set_weight("Alpha", "Fun", 3).unwrap();
+        workspace.set_weight("Beta", "Fun", 2).unwrap();
+        workspace.set_weight("Gamma", "Fun", 1).unwrap();
+
+        workspace
+    }
+
+    #[test]
+    fn test_take_snapshot() {
+        let mut ws = build_test_workspace();
+        ws.archive_project("Gamma").unwrap();
+        ws.take_snapshot("Sprint 1").unwrap();
+
+        let snapshot = &ws.get_snapshots()[0];
+        assert_eq!("Sprint 1", snapshot.get_label());
+        assert_eq!(Method::WeightedSum, snapshot.get_method());
+        let scores = snapshot.get_scores("Workday").unwrap();
+        assert_eq!(2, scores.len());
+        assert_eq!(3.0, scores["Alpha"]);
+
+        assert!(ws.take_snapshot("Sprint 1").is_err());
+    }
+
+    #[test]
+    fn test_trend() {
+        let mut ws = build_test_workspace();
+        ws.take_snapshot("Sprint 1").unwrap();
+        ws.set_weight("Gamma", "Fun", 4).unwrap();
+        ws.remove_project("Beta").unwrap();
+        ws.add_project(Project::new("Delta")).unwrap();
+        ws.take_snapshot("Sprint 2").unwrap();
+        ws.add_priority_set("Weekend").unwrap();
+        ws.take_snapshot("Sprint 3").unwrap();
+
+        let trend = trend(&ws, "Workday").unwrap();
+
+        assert_eq!(vec!["Sprint 1", "Sprint 2", "Sprint 3"], trend.snapshots);
+        assert_eq!(vec!["Gamma", "Alpha", "Delta", "Beta"], trend.projects);
+        assert_eq!(vec![Some(3), Some(1), Some(1)], trend.ranks[0]);
+        assert_eq!(vec![Some(1.0), Some(4.0), Some(4.0)], trend.scores[0]);
+        assert_eq!(vec![Some(2), None, None], trend.ranks[3]);
+        assert_eq!(Some(2), trend.rank_change(0));
+        assert_eq!(Some(-1), trend.rank_change(1));
+        assert_eq!(None, trend.rank_change(3));
+        assert_eq!(vec![0, 1], trend.movers(3));
+
+        assert_eq!(1, super::trend(&ws, "Weekend").unwrap().snapshots.len());
+        assert!(super::trend(&ws, "Holiday").is_err());
+    }
+
+    #[test]
+    fn test_trend_with_ties() {
+        let mut ws = build_test_workspace();
+        ws.set_weight("Beta", "Fun", 3).unwrap();
+        ws.take_snapshot("Sprint 1").unwrap();
+        ws.reorder_projects(&["Beta".to_string()]).unwrap();
+        ws.take_snapshot("Sprint 2").unwrap();
+
+        let trend = trend(&ws, "Workday").unwrap();
+
+        assert_eq!(vec![Some(1), Some(1)], trend.ranks[0]);
+        assert_eq!(vec![Some(1), Some(1)], trend.ranks[1]);
+        assert_eq!(vec![Some(3), Some(3)], trend.ranks[2]);
+        assert!(trend.movers(3).is_empty());
+    }
+}
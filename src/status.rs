@@ -0,0 +1,144 @@
+//! Stages in the life of a project and when the project reached them.
+
+use std::{
+    fmt::{self, Display, Formatter},
+    str::FromStr,
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+use serde::{Deserialize, Serialize};
+use toml::value::{Date, Datetime, Offset, Time};
+
+use crate::RecicionError;
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum Status {
+    #[default]
+    Idea,
+    Active,
+    Paused,
+    Done,
+    Dropped,
+}
+
+impl Status {
+    pub const ALL: [Status; 5] = [
+        Status::Idea,
+        Status::Active,
+        Status::Paused,
+        Status::Done,
+        Status::Dropped,
+    ];
+
+    /// Whether the project is finished, either done or dropped.
+    pub fn is_closed(self) -> bool {
+        matches!(self, Status::Done | Status::Dropped)
+    }
+}
+
+impl Display for Status {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            Status::Idea => write!(f, "idea"),
+            Status::Active => write!(f, "active"),
+            Status::Paused => write!(f, "paused"),
+            Status::Done => write!(f, "done"),
+            Status::Dropped => write!(f, "dropped"),
+        }
+    }
+}
+
+impl FromStr for Status {
+    type Err = RecicionError;
+
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        Status::ALL
+            .into_iter()
+            .find(|status| status.to_string() == s)
+            .ok_or(RecicionError::new(format!("no status {}", s)))
+    }
+}
+
+/// An entry in the status history of a project.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct StatusChange {
+    pub status: Status,
+    pub at: Datetime,
+}
+
+/// Returns the current time in UTC with a precision of seconds.
+pub(crate) fn now() -> Datetime {
+    let seconds = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .expect("system clock is after the Unix epoch")
+        .as_secs();
+    datetime_from_unix(seconds)
+}
+
+/// Converts seconds since the Unix epoch into a UTC datetime.
+fn datetime_from_unix(seconds: u64) -> Datetime {
+    let days = seconds / 86_400;
+    let seconds_of_day = seconds % 86_400;
+
+    // civil date from the number of days, counting in eras of 400 years that start in March
+    let days = days + 719_468;
+    let era = days / 146_097;
+    let day_of_era = days % 146_097;
+    let year_of_era =
+        (day_of_era - day_of_era / 1460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let month_from_march = (5 * day_of_year + 2) / 153;
+    let day = day_of_year - (153 * month_from_march + 2) / 5 + 1;
+    let month = if month_from_march < 10 {
+        month_from_march + 3
+    } else {
+        month_from_march - 9
+    };
+    let year = year_of_era + era * 400 + u64::from(month <= 2);
+
+    Datetime {
+        date: Some(Date {
+            year: year as u16,
+            month: month as u8,
+            day: day as u8,
+        }),
+        time: Some(Time {
+            hour: (seconds_of_day / 3600) as u8,
+            minute: (seconds_of_day % 3600 / 60) as u8,
+            second: (seconds_of_day % 60) as u8,
+            nanosecond: 0,
+        }),
+        offset: Some(Offset::Z),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_status_from_str() {
+        assert_eq!(Status::Paused, "paused".parse::<Status>().unwrap());
+        for status in Status::ALL {
+            assert_eq!(status, status.to_string().parse::<Status>().unwrap());
+        }
+        assert!("finished".parse::<Status>().is_err());
+    }
+
+    #[test]
+    fn test_datetime_from_unix() {
+        assert_eq!(
+            "1970-01-01T00:00:00Z",
+            datetime_from_unix(0).to_string().as_str()
+        );
+        assert_eq!(
+            "2000-02-29T23:59:59Z",
+            datetime_from_unix(951_868_799).to_string().as_str()
+        );
+        assert_eq!(
+            "2024-12-31T12:30:05Z",
+            datetime_from_unix(1_735_648_205).to_string().as_str()
+        );
+    }
+}
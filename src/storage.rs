@@ -0,0 +1,279 @@
+//! Writing files so that a crash or a full disk never leaves them truncated or missing, and
+//! locking them against concurrent changes.
+
+use std::{
+    env,
+    ffi::OsString,
+    fs::{self, File, OpenOptions, Permissions, TryLockError},
+    io::{self, Read, Write},
+    path::{Path, PathBuf},
+    process, thread,
+    time::{Duration, Instant},
+};
+
+use anyhow::{Context, Result};
+use serde::Serialize;
+
+use crate::RecicionError;
+
+/// Time between attempts to take a lock that is held by another process.
+const LOCK_POLL_INTERVAL: Duration = Duration::from_millis(50);
+
+/// Serializes a value to TOML and writes it atomically, see [`write_atomically`].
+///
+/// The file is left untouched if the value can't be serialized.
+pub fn write_toml<T: Serialize + ?Sized>(value: &T, path: &Path, backup: bool) -> Result<()> {
+    let toml_string = toml::to_string_pretty(value)
+        .with_context(|| format!("serializing contents of {}", path.display()))?;
+    write_atomically(path, toml_string.as_bytes(), backup)
+}
+
+/// Replaces the contents of a file without a moment in which the file is missing or incomplete.
+///
+/// The contents are written to a temporary file next to the target, flushed to disk and then
+/// renamed over the target. A symlink is followed, so that the file it points to is replaced
+/// rather than the link, and the permissions of the file are kept. With `backup`, the previous
+/// version of the file is kept at the path returned by [`backup_path`].
+pub fn write_atomically(path: &Path, contents: &[u8], backup: bool) -> Result<()> {
+    let path = &resolve_symlinks(path)?;
+    let permissions = match fs::metadata(path) {
+        Ok(metadata) => Some(metadata.permissions()),
+        Err(error) if error.kind() == io::ErrorKind::NotFound => None,
+        Err(error) => return Err(error).with_context(|| format!("reading {}", path.display())),
+    };
+
+    let temp_path = sibling_path(path, &format!(".{}.tmp", process::id()));
+    if let Err(error) = write_synced(&temp_path, contents, permissions) {
+        let _ = fs::remove_file(&temp_path);
+        return Err(error.context(format!("writing temporary file {}", temp_path.display())));
+    }
+
+    if backup && path.exists() {
+        fs::copy(path, backup_path(path))
+            .with_context(|| format!("backing up {}", path.display()))?;
+    }
+    if let Err(error) = fs::rename(&temp_path, path) {
+        let _ = fs::remove_file(&temp_path);
+        return Err(error).with_context(|| format!("replacing {}", path.display()));
+    }
+    sync_parent_dir(path);
+
+    Ok(())
+}
+
+/// Returns the path that [`write_atomically`] keeps the previous version of a file at.
+pub fn backup_path(path: &Path) -> PathBuf {
+    sibling_path(path, ".bak")
+}
+
+/// Advisory lock that keeps other processes from changing a file, released when dropped.
+///
+/// The lock is held on a lock file next to the file, which also records the process holding the
+/// lock so that waiting processes can name it. Processes that don't take the lock aren't kept
+/// from changing the file.
+#[derive(Debug)]
+pub struct FileLock {
+    file: File,
+}
+
+impl FileLock {
+    /// Locks a file, waiting up to `timeout` for another process to release it.
+    pub fn acquire(path: &Path, timeout: Duration) -> Result<Self> {
+        let lock_path = lock_path(path);
+        let mut file = OpenOptions::new()
+            .read(true)
+            .write(true)
+            .create(true)
+            .truncate(false)
+            .open(&lock_path)
+            .with_context(|| format!("opening lock file {}", lock_path.display()))?;
+
+        let start = Instant::now();
+        loop {
+            match file.try_lock() {
+                Ok(()) => break,
+                Err(TryLockError::WouldBlock) if start.elapsed() < timeout => {
+                    thread::sleep(LOCK_POLL_INTERVAL)
+                }
+                Err(TryLockError::WouldBlock) => {
+                    let mut holder = String::new();
+                    let _ = file.read_to_string(&mut holder);
+                    return Err(RecicionError::new(format!(
+                        "{} is locked by {}, gave up waiting after {} seconds",
+                        path.display(),
+                        describe_holder(&holder),
+                        timeout.as_secs_f64()
+                    ))
+                    .into());
+                }
+                Err(TryLockError::Error(error)) => {
+                    return Err(error).with_context(|| format!("locking {}", path.display()))
+                }
+            }
+        }
+
+        let command: Vec<_> = env::args().collect();
+        file.set_len(0)?;
+        file.write_all(format!("{}\n{}\n", process::id(), command.join(" ")).as_bytes())?;
+
+        Ok(Self { file })
+    }
+}
+
+impl Drop for FileLock {
+    fn drop(&mut self) {
+        // the lock file stays, since removing it could let two processes lock different files
+        let _ = self.file.set_len(0);
+        let _ = self.file.unlock();
+    }
+}
+
+/// Returns the path of the lock file that [`FileLock`] locks a file with.
+pub fn lock_path(path: &Path) -> PathBuf {
+    sibling_path(path, ".lock")
+}
+
+/// Describes the process holding a lock by the process ID and command in the lock file.
+fn describe_holder(contents: &str) -> String {
+    let mut lines = contents.lines();
+    match (lines.next(), lines.next()) {
+        (Some(pid), Some(command)) => format!("process {} ({})", pid, command),
+        (Some(pid), None) => format!("process {}", pid),
+        _ => "another process".into(),
+    }
+}
+
+/// Returns the path of the file with the suffix appended to its name.
+pub(crate) fn sibling_path(path: &Path, suffix: &str) -> PathBuf {
+    let mut name = path.file_name().map(OsString::from).unwrap_or_default();
+    name.push(suffix);
+    path.with_file_name(name)
+}
+
+/// Returns the path of the file that a symlink points to, or the path itself if it isn't a link
+/// or doesn't exist yet.
+fn resolve_symlinks(path: &Path) -> Result<PathBuf> {
+    match fs::canonicalize(path) {
+        Ok(resolved) => Ok(resolved),
+        Err(error) if error.kind() == io::ErrorKind::NotFound => Ok(path.to_path_buf()),
+        Err(error) => Err(error).with_context(|| format!("resolving {}", path.display())),
+    }
+}
+
+fn write_synced(path: &Path, contents: &[u8], permissions: Option<Permissions>) -> Result<()> {
+    let mut file = File::create(path)?;
+    file.write_all(contents)?;
+    if let Some(permissions) = permissions {
+        file.set_permissions(permissions)?;
+    }
+    file.sync_all()?;
+
+    Ok(())
+}
+
+/// Flushes the rename to disk, which only works and is only necessary on Unix.
+#[cfg(unix)]
+fn sync_parent_dir(path: &Path) {
+    let parent = match path.parent() {
+        Some(parent) if !parent.as_os_str().is_empty() => parent,
+        _ => Path::new("."),
+    };
+    if let Ok(dir) = File::open(parent) {
+        let _ = dir.sync_all();
+    }
+}
+
+#[cfg(not(unix))]
+fn sync_parent_dir(_path: &Path) {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde::{ser::Error, Serializer};
+    use tempfile::tempdir;
+
+    struct Unserializable;
+
+    impl Serialize for Unserializable {
+        fn serialize<S: Serializer>(&self, _serializer: S) -> Result<S::Ok, S::Error> {
+            Err(S::Error::custom("unserializable"))
+        }
+    }
+
+    #[test]
+    fn test_write_atomically() {
+        let dir = tempdir().unwrap();
+        let path = dir.path().join("replace.toml");
+        fs::write(&path, "old").unwrap();
+
+        write_atomically(&path, b"new", true).unwrap();
+
+        assert_eq!("new", fs::read_to_string(&path).unwrap());
+        assert_eq!("old", fs::read_to_string(backup_path(&path)).unwrap());
+        fs::remove_file(backup_path(&path)).unwrap();
+
+        write_atomically(&path, b"newer", false).unwrap();
+
+        assert_eq!("newer", fs::read_to_string(&path).unwrap());
+        assert!(!backup_path(&path).exists());
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_write_atomically_keeps_symlink_and_permissions() {
+        use std::os::unix::fs::{symlink, PermissionsExt};
+
+        let dir = tempdir().unwrap();
+        let target = dir.path().join("target.toml");
+        let link = dir.path().join("link.toml");
+        fs::write(&target, "old").unwrap();
+        fs::set_permissions(&target, Permissions::from_mode(0o600)).unwrap();
+        symlink(&target, &link).unwrap();
+
+        write_atomically(&link, b"new", false).unwrap();
+
+        assert!(fs::symlink_metadata(&link).unwrap().is_symlink());
+        assert_eq!("new", fs::read_to_string(&target).unwrap());
+        let mode = fs::metadata(&target).unwrap().permissions().mode();
+        assert_eq!(0o600, mode & 0o777);
+    }
+
+    #[test]
+    fn test_failed_serialization_keeps_file() {
+        let dir = tempdir().unwrap();
+        let path = dir.path().join("workspace.toml");
+        fs::write(&path, "name = \"original\"\n").unwrap();
+
+        assert!(write_toml(&Unserializable, &path, true).is_err());
+
+        assert_eq!("name = \"original\"\n", fs::read_to_string(&path).unwrap());
+        // neither a temporary file nor a backup is left behind
+        assert_eq!(1, fs::read_dir(dir.path()).unwrap().count());
+    }
+
+    #[test]
+    fn test_file_lock() {
+        let dir = tempdir().unwrap();
+        let path = dir.path().join("workspace.toml");
+
+        let lock = FileLock::acquire(&path, Duration::ZERO).unwrap();
+        let error = FileLock::acquire(&path, Duration::from_millis(100)).unwrap_err();
+        assert!(error.to_string().starts_with(&format!(
+            "{} is locked by process {} (",
+            path.display(),
+            process::id()
+        )));
+
+        drop(lock);
+        FileLock::acquire(&path, Duration::ZERO).unwrap();
+    }
+
+    #[test]
+    fn test_describe_holder() {
+        assert_eq!(
+            "process 42 (recision weight set A Fun 3)",
+            describe_holder("42\nrecision weight set A Fun 3\n")
+        );
+        assert_eq!("another process", describe_holder(""));
+    }
+}
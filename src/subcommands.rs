@@ -1,14 +1,99 @@
-use std::path::PathBuf;
+use std::{env, fs, io, path::PathBuf};
 
-use anyhow::Result;
+use anyhow::{Context, Result};
 use clap::ArgMatches;
 use path_absolutize::Absolutize;
-use recision::{RecicionError, Workspace};
+use recision::{
+    analysis::RobustnessOptions,
+    dependency::{self, DependencyMode},
+    journal::{self, Operation},
+    scoring::Method,
+    storage::FileLock,
+    Constraint, Direction, RecicionError, Status, Weight, Workspace,
+};
+use terminal_size::{terminal_size, Width};
 
+mod analyze;
+mod criterion;
+mod display;
+mod dominance;
+mod explain;
+mod history;
+mod portfolio;
+mod priority_set;
 mod project;
+mod snapshot;
+mod weight;
 mod workspace;
 
-use crate::config::{get_configuration, DefaultConfigDirProvider};
+use crate::cli::build_cli;
+use crate::config::{get_configuration, Config, DefaultConfigDirProvider};
+
+/// Width assumed for truncation when the terminal size can't be determined.
+const DEFAULT_TERMINAL_WIDTH: usize = 80;
+
+/// Returns the absolute path of the workspace file that is currently active in the configuration.
+fn workspace_path(config: &Config) -> Result<PathBuf> {
+    let path = config
+        .get_workspace()
+        .clone()
+        .ok_or(RecicionError::new("no active workspace".into()))?;
+    Ok(path
+        .absolutize()
+        .with_context(|| format!("resolving workspace path {}", path.display()))?
+        .to_path_buf())
+}
+
+/// Reads the workspace that is currently active in the configuration.
+fn read_workspace(config: &Config) -> Result<Workspace> {
+    Workspace::read_from_file(workspace_path(config)?)
+}
+
+/// Locks the workspace file that is currently active in the configuration, so that other
+/// processes can't change it between reading and writing it back.
+fn lock_workspace(config: &Config) -> Result<FileLock> {
+    FileLock::acquire(&workspace_path(config)?, config.get_lock_timeout())
+}
+
+/// Locks the workspace like [`lock_workspace`] unless the subcommand only reads it, which needs
+/// no lock since the file is always replaced as a whole.
+fn lock_workspace_unless(config: &Config, read_only: bool) -> Result<Option<FileLock>> {
+    if read_only {
+        return Ok(None);
+    }
+    lock_workspace(config).map(Some)
+}
+
+/// Writes the workspace back to the file that is currently active in the configuration and
+/// records the change in the journal of the workspace.
+fn write_workspace(config: &Config, workspace: &Workspace) -> Result<()> {
+    let path = workspace_path(config)?;
+    let before = fs::read_to_string(&path).unwrap_or_default();
+    workspace.write_to_file(path.clone(), config.keeps_backup())?;
+    let after = fs::read_to_string(&path)?;
+
+    journal::record(&path, operation(), &before, &after)
+}
+
+/// Returns the subcommand the program was called with and its arguments, to describe a change
+/// in the journal.
+fn operation() -> Operation {
+    let args: Vec<_> = env::args().collect();
+    let mut kind = Vec::new();
+    if let Ok(matches) = build_cli().try_get_matches_from(&args) {
+        let mut matches = &matches;
+        // aliases are resolved to the names of the subcommands
+        while let Some((name, submatches)) = matches.subcommand() {
+            kind.push(name.to_string());
+            matches = submatches;
+        }
+    }
+
+    Operation {
+        args: args.into_iter().skip(1 + kind.len()).collect(),
+        kind: kind.join(" "),
+    }
+}
 
 pub fn run_workspace(matches: &ArgMatches) -> Result<()> {
     let mut config = get_configuration(&DefaultConfigDirProvider {})?;
@@ -24,6 +109,9 @@ pub fn run_workspace(matches: &ArgMatches) -> Result<()> {
         Some(("deactivate", _)) => {
             workspace::deactivate(&mut config)?;
         }
+        Some(("migrate", argmatches)) => {
+            workspace::migrate(&config, argmatches.get_flag("check"))?;
+        }
         _ => {
             workspace::status(&mut config)?;
         }
@@ -34,19 +122,16 @@ pub fn run_workspace(matches: &ArgMatches) -> Result<()> {
 
 pub fn run_project(matches: &ArgMatches) -> Result<()> {
     let config = get_configuration(&DefaultConfigDirProvider {})?;
-    #[allow(unused_mut)] // TODO shut clippy up for now
-    let mut workspace = Workspace::read_from_file(
-        config
-            .get_workspace()
-            .clone()
-            .ok_or(RecicionError::new("no active workspace".into()))?
-            .absolutize()
-            .unwrap()
-            .to_path_buf(),
-    )?;
+    let read_only = match matches.subcommand() {
+        Some(("list" | "deps", _)) => true,
+        Some(("status", argmatches)) => argmatches.get_one::<String>("STATUS").is_none(),
+        _ => false,
+    };
+    let _lock = lock_workspace_unless(&config, read_only)?;
+    let workspace = read_workspace(&config)?;
 
     match matches.subcommand() {
-        Some(("list", _)) => project::list(workspace),
+        Some(("list", argmatches)) => project::list(workspace, argmatches.get_flag("archived")),
         Some(("add", argmatches)) => {
             let project = argmatches
                 .get_one::<String>("PROJECT_NAME")
@@ -59,8 +144,8 @@ pub fn run_project(matches: &ArgMatches) -> Result<()> {
                 .expect("required")
                 .cloned()
                 .collect();
-            println!("Removing projects {:?}", projects);
-            todo!();
+            let dry_run = argmatches.get_flag("dry-run");
+            project::remove(config, workspace, &projects, dry_run)
         }
         Some(("reorder", argmatches)) => {
             let projects: Vec<_> = argmatches
@@ -68,29 +153,385 @@ pub fn run_project(matches: &ArgMatches) -> Result<()> {
                 .expect("required")
                 .cloned()
                 .collect();
-            println!("Reordering projects {:?}", projects);
-            todo!();
+            let dry_run = argmatches.get_flag("dry-run");
+            project::reorder(config, workspace, &projects, dry_run)
+        }
+        Some(("depend", argmatches)) => {
+            let project = argmatches.get_one::<String>("PROJECT").expect("required");
+            let prerequisites: Vec<_> = argmatches
+                .get_many::<String>("PREREQUISITE")
+                .expect("required")
+                .cloned()
+                .collect();
+            project::depend(config, workspace, project, &prerequisites)
+        }
+        Some(("undepend", argmatches)) => {
+            let project = argmatches.get_one::<String>("PROJECT").expect("required");
+            let prerequisites: Vec<_> = argmatches
+                .get_many::<String>("PREREQUISITE")
+                .expect("required")
+                .cloned()
+                .collect();
+            project::undepend(config, workspace, project, &prerequisites)
+        }
+        Some(("status", argmatches)) => {
+            let project = argmatches.get_one::<String>("PROJECT").expect("required");
+            let status = argmatches
+                .get_one::<String>("STATUS")
+                .map(|status| status.parse::<Status>())
+                .transpose()?;
+            project::status(config, workspace, project, status)
+        }
+        Some(("archive", argmatches)) => {
+            let projects: Vec<_> = argmatches
+                .get_many::<String>("PROJECT")
+                .expect("required")
+                .cloned()
+                .collect();
+            project::archive(config, workspace, &projects)
+        }
+        Some(("restore", argmatches)) => {
+            let projects: Vec<_> = argmatches
+                .get_many::<String>("PROJECT")
+                .expect("required")
+                .cloned()
+                .collect();
+            project::restore(config, workspace, &projects)
+        }
+        Some(("deps", argmatches)) => {
+            let project = argmatches.get_one::<String>("PROJECT").map(String::as_str);
+            project::deps(workspace, project, argmatches.get_flag("ascii"))
         }
         _ => unreachable!("no default behavior for project subcommand"),
     }
 }
 
-#[allow(unused)]
 pub fn run_criterion(matches: &ArgMatches) -> Result<()> {
-    todo!();
+    let config = get_configuration(&DefaultConfigDirProvider {})?;
+    let _lock = lock_workspace_unless(&config, matches.subcommand_name() == Some("list"))?;
+    let workspace = read_workspace(&config)?;
+
+    match matches.subcommand() {
+        Some(("list", _)) => criterion::list(workspace),
+        Some(("add", argmatches)) => {
+            let name = argmatches.get_one::<String>("NAME").expect("required");
+            let priority = argmatches.get_one::<f64>("PRIORITY").copied();
+            criterion::add(
+                config,
+                workspace,
+                name,
+                priority,
+                criterion_details(argmatches),
+            )
+        }
+        Some(("update", argmatches)) => {
+            let criterion = argmatches.get_one::<String>("CRITERION").expect("required");
+            criterion::update(config, workspace, criterion, criterion_details(argmatches))
+        }
+        Some(("remove", argmatches)) => {
+            let criteria: Vec<_> = argmatches
+                .get_many::<String>("CRITERION")
+                .expect("required")
+                .cloned()
+                .collect();
+            criterion::remove(config, workspace, &criteria)
+        }
+        Some(("reorder", argmatches)) => {
+            let criteria: Vec<_> = argmatches
+                .get_many::<String>("CRITERION")
+                .expect("required")
+                .cloned()
+                .collect();
+            criterion::reorder(config, workspace, &criteria)
+        }
+        Some(("update-priority", argmatches)) => {
+            let criterion = argmatches.get_one::<String>("CRITERION").expect("required");
+            let priority = *argmatches.get_one::<f64>("PRIORITY").expect("required");
+            criterion::update_priority(config, workspace, criterion, priority)
+        }
+        _ => unreachable!("no default behavior for criterion subcommand"),
+    }
+}
+
+fn criterion_details(matches: &ArgMatches) -> criterion::CriterionDetails {
+    let direction = if matches.get_flag("cost") {
+        Some(Direction::Cost)
+    } else if matches.get_flag("benefit") {
+        Some(Direction::Benefit)
+    } else {
+        None
+    };
+
+    criterion::CriterionDetails {
+        direction,
+        min: matches.get_one::<f64>("min").copied(),
+        max: matches.get_one::<f64>("max").copied(),
+        unit: matches.get_one::<String>("unit").cloned(),
+        description: matches.get_one::<String>("description").cloned(),
+        budget_cost: matches.get_flag("budget-cost"),
+    }
 }
 
-#[allow(unused)]
 pub fn run_priority_set(matches: &ArgMatches) -> Result<()> {
-    todo!();
+    let config = get_configuration(&DefaultConfigDirProvider {})?;
+    // compare locks the workspace itself, so that the lock isn't held while waiting for input
+    let _lock = lock_workspace_unless(
+        &config,
+        matches!(matches.subcommand_name(), Some("list" | "compare")),
+    )?;
+    let workspace = read_workspace(&config)?;
+
+    match matches.subcommand() {
+        Some(("list", _)) => priority_set::list(workspace),
+        Some(("add", argmatches)) => {
+            let name = argmatches.get_one::<String>("NAME").expect("required");
+            priority_set::add(config, workspace, name)
+        }
+        Some(("remove", argmatches)) => {
+            let priority_sets: Vec<_> = argmatches
+                .get_many::<String>("PS")
+                .expect("required")
+                .cloned()
+                .collect();
+            priority_set::remove(config, workspace, &priority_sets)
+        }
+        Some(("reorder", argmatches)) => {
+            let priority_sets: Vec<_> = argmatches
+                .get_many::<String>("PS")
+                .expect("required")
+                .cloned()
+                .collect();
+            priority_set::reorder(config, workspace, &priority_sets)
+        }
+        Some(("activate", argmatches)) => {
+            let priority_set = argmatches.get_one::<String>("PS").expect("required");
+            priority_set::activate(config, workspace, priority_set)
+        }
+        Some(("compare", argmatches)) => {
+            let priority_set = argmatches.get_one::<String>("PS").map(String::as_str);
+            let force = argmatches.get_flag("force");
+            priority_set::compare(
+                config,
+                workspace,
+                priority_set,
+                force,
+                &mut io::stdin().lock(),
+            )
+        }
+        Some(("constrain", argmatches)) => {
+            let criterion = argmatches.get_one::<String>("CRITERION").expect("required");
+            let priority_set = argmatches.get_one::<String>("set").map(String::as_str);
+            let constraint = Constraint::new(
+                argmatches.get_one::<f64>("min").copied(),
+                argmatches.get_one::<f64>("max").copied(),
+            )?;
+            priority_set::constrain(config, workspace, priority_set, criterion, constraint)
+        }
+        Some(("unconstrain", argmatches)) => {
+            let criterion = argmatches.get_one::<String>("CRITERION").expect("required");
+            let priority_set = argmatches.get_one::<String>("set").map(String::as_str);
+            priority_set::unconstrain(config, workspace, priority_set, criterion)
+        }
+        _ => unreachable!("no default behavior for priority-set subcommand"),
+    }
 }
 
-#[allow(unused)]
 pub fn run_weight(matches: &ArgMatches) -> Result<()> {
-    todo!();
+    let config = get_configuration(&DefaultConfigDirProvider {})?;
+    let _lock = lock_workspace_unless(&config, matches.subcommand_name() == Some("get"))?;
+    let workspace = read_workspace(&config)?;
+
+    match matches.subcommand() {
+        Some(("get", argmatches)) => {
+            let project = argmatches.get_one::<String>("PROJECT").expect("required");
+            let criterion = argmatches.get_one::<String>("CRITERION").expect("required");
+            weight::get(workspace, project, criterion)
+        }
+        Some(("set", argmatches)) => {
+            let project = argmatches.get_one::<String>("PROJECT").expect("required");
+            let criterion = argmatches.get_one::<String>("CRITERION").expect("required");
+            let weight = argmatches
+                .get_one::<String>("WEIGHT")
+                .expect("required")
+                .parse::<Weight>()?;
+            weight::set(config, workspace, project, criterion, weight)
+        }
+        _ => unreachable!("no default behavior for weight subcommand"),
+    }
 }
 
-#[allow(unused)]
 pub fn run_display(matches: &ArgMatches) -> Result<()> {
-    todo!();
+    let config = get_configuration(&DefaultConfigDirProvider {})?;
+    let workspace = read_workspace(&config)?;
+
+    let width = match matches.get_one::<usize>("width") {
+        Some(width) => Some(*width),
+        None if matches.get_flag("truncate") => Some(
+            terminal_size()
+                .map(|(Width(width), _)| width as usize)
+                .unwrap_or(DEFAULT_TERMINAL_WIDTH),
+        ),
+        None => None,
+    };
+    let method = matches
+        .get_one::<String>("method")
+        .map(|method| method.parse::<Method>())
+        .transpose()?;
+    let options = display::DisplayOptions {
+        width,
+        ascii: matches.get_flag("ascii"),
+        method,
+        all_sets: matches.get_flag("all-sets"),
+        archived: matches.get_flag("archived"),
+        closed: matches.get_flag("closed"),
+        dependencies: matches
+            .get_one::<String>("dependencies")
+            .map(|mode| match mode.as_str() {
+                "prerequisites" => DependencyMode::Prerequisites,
+                _ => DependencyMode::Inherit {
+                    share: matches
+                        .get_one::<f64>("share")
+                        .copied()
+                        .unwrap_or(dependency::DEFAULT_SHARE),
+                },
+            }),
+    };
+
+    display::display(workspace, &options)
+}
+
+pub fn run_explain(matches: &ArgMatches) -> Result<()> {
+    let config = get_configuration(&DefaultConfigDirProvider {})?;
+    let workspace = read_workspace(&config)?;
+
+    // IDs refer to the whole workspace, so they are resolved before projects are left out
+    let project =
+        workspace.find_project(matches.get_one::<String>("PROJECT").expect("required"))?;
+    let other = matches
+        .get_one::<String>("OTHER")
+        .map(|other| workspace.find_project(other))
+        .transpose()?;
+    for name in [Some(&project), other.as_ref()].into_iter().flatten() {
+        let candidate = workspace
+            .get_projects()
+            .iter()
+            .find(|p| p.get_name() == name)
+            .expect("resolved project is in the workspace");
+        if candidate.is_archived() {
+            return Err(RecicionError::new(format!("project {} is archived", name)).into());
+        }
+        if candidate.get_status().is_closed() {
+            return Err(RecicionError::new(format!(
+                "project {} is {}",
+                name,
+                candidate.get_status()
+            ))
+            .into());
+        }
+    }
+    let workspace = workspace.without_archived().without_closed();
+    let vetoes = workspace.find_vetoes()?;
+    for name in [Some(&project), other.as_ref()].into_iter().flatten() {
+        if let Some(veto) = vetoes.iter().find(|veto| &veto.project == name) {
+            return Err(
+                RecicionError::new(format!("project {} is vetoed ({})", name, veto)).into(),
+            );
+        }
+    }
+    let mut workspace = workspace.without_vetoed()?;
+    if let Some(method) = matches
+        .get_one::<String>("method")
+        .map(|method| method.parse::<Method>())
+        .transpose()?
+    {
+        workspace.set_scoring_method(method);
+    }
+    match other {
+        Some(other) => explain::compare(workspace, &project, &other),
+        None => explain::explain(workspace, &project),
+    }
+}
+
+pub fn run_portfolio(matches: &ArgMatches) -> Result<()> {
+    let config = get_configuration(&DefaultConfigDirProvider {})?;
+    let workspace = read_workspace(&config)?.without_archived().without_closed();
+
+    let budget = *matches.get_one::<f64>("budget").expect("required");
+    let criterion = matches.get_one::<String>("criterion").map(String::as_str);
+    let alternatives = *matches.get_one::<usize>("alternatives").expect("required");
+    portfolio::portfolio(workspace, budget, criterion, alternatives)
+}
+
+pub fn run_dominance() -> Result<()> {
+    let config = get_configuration(&DefaultConfigDirProvider {})?;
+    let workspace = read_workspace(&config)?.without_archived().without_closed();
+
+    dominance::dominance(workspace)
+}
+
+pub fn run_analyze(matches: &ArgMatches) -> Result<()> {
+    let config = get_configuration(&DefaultConfigDirProvider {})?;
+    let workspace = read_workspace(&config)?.without_archived().without_closed();
+
+    match matches.subcommand() {
+        Some(("robustness", submatches)) => {
+            let options = RobustnessOptions {
+                iterations: *submatches.get_one::<usize>("iterations").expect("required"),
+                seed: *submatches.get_one::<u64>("seed").expect("required"),
+                priority_variation: *submatches
+                    .get_one::<f64>("priority-variation")
+                    .expect("required"),
+                weight_variation: *submatches
+                    .get_one::<f64>("weight-variation")
+                    .expect("required"),
+            };
+            let method = submatches
+                .get_one::<String>("method")
+                .map(|method| method.parse::<Method>())
+                .transpose()?;
+            analyze::robustness(workspace, &options, method)?
+        }
+        Some(("sensitivity", _)) => analyze::sensitivity(workspace)?,
+        _ => unreachable!("no default behavior for analyze subcommand"),
+    }
+
+    Ok(())
+}
+
+pub fn run_snapshot(matches: &ArgMatches) -> Result<()> {
+    let config = get_configuration(&DefaultConfigDirProvider {})?;
+    let _lock = lock_workspace(&config)?;
+    let workspace = read_workspace(&config)?;
+
+    let label = matches.get_one::<String>("LABEL").expect("required");
+    snapshot::snapshot(config, workspace, label)
+}
+
+pub fn run_trend(matches: &ArgMatches) -> Result<()> {
+    let config = get_configuration(&DefaultConfigDirProvider {})?;
+    let workspace = read_workspace(&config)?;
+
+    let priority_set = matches.get_one::<String>("set").map(String::as_str);
+    let movers = *matches.get_one::<usize>("movers").expect("required");
+    snapshot::trend(workspace, priority_set, movers)
+}
+
+pub fn run_undo() -> Result<()> {
+    let config = get_configuration(&DefaultConfigDirProvider {})?;
+    let _lock = lock_workspace(&config)?;
+
+    history::undo(&config)
+}
+
+pub fn run_redo() -> Result<()> {
+    let config = get_configuration(&DefaultConfigDirProvider {})?;
+    let _lock = lock_workspace(&config)?;
+
+    history::redo(&config)
+}
+
+pub fn run_history() -> Result<()> {
+    let config = get_configuration(&DefaultConfigDirProvider {})?;
+
+    history::history(&config)
 }
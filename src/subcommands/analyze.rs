@@ -0,0 +1,128 @@
+use anyhow::Result;
+use recision::{
+    analysis::{self, BreakEven, RobustnessOptions},
+    scoring::Method,
+    Workspace,
+};
+
+use super::display::{format_number, plain_table};
+
+pub fn robustness(
+    mut workspace: Workspace,
+    options: &RobustnessOptions,
+    method: Option<Method>,
+) -> Result<()> {
+    if workspace.get_project_names().is_empty() {
+        println!("No projects in workspace");
+        return Ok(());
+    }
+    if let Some(method) = method {
+        workspace.set_scoring_method(method);
+    }
+    let pool = workspace.without_vetoed()?;
+    if pool.get_project_names().is_empty() {
+        println!("All projects are vetoed");
+        return Ok(());
+    }
+
+    let report = analysis::robustness(&pool, options)?;
+
+    let mut order: Vec<_> = (0..report.projects.len()).collect();
+    order.sort_by(|a, b| report.mean_rank(*a).total_cmp(&report.mean_rank(*b)));
+
+    let mut rows = vec![vec![
+        "Project".to_string(),
+        "First".into(),
+        "Mean rank".into(),
+    ]];
+    rows[0].extend((1..=report.projects.len()).map(|rank| format!("#{rank}")));
+    for index in &order {
+        let mut row = vec![
+            report.projects[*index].clone(),
+            format_percent(report.first_probability(*index)),
+            format!("{:.2}", report.mean_rank(*index)),
+        ];
+        row.extend(
+            (0..report.projects.len())
+                .map(|rank| format_percent(report.rank_probability(*index, rank))),
+        );
+        rows.push(row);
+    }
+    print!("{}", plain_table(&rows));
+
+    let leader = order
+        .iter()
+        .max_by(|a, b| {
+            report
+                .first_probability(**a)
+                .total_cmp(&report.first_probability(**b))
+                .then(b.cmp(a))
+        })
+        .expect("there is at least one project");
+    println!(
+        "\n'{}' ranks first in {} of {} iterations (seed {})",
+        report.projects[*leader],
+        format_percent(report.first_probability(*leader)),
+        report.iterations,
+        options.seed
+    );
+
+    Ok(())
+}
+
+pub fn sensitivity(workspace: Workspace) -> Result<()> {
+    if workspace.get_project_names().is_empty() {
+        println!("No projects in workspace");
+        return Ok(());
+    }
+
+    let pool = workspace.without_vetoed()?;
+    if pool.get_project_names().is_empty() {
+        println!("All projects are vetoed");
+        return Ok(());
+    }
+
+    let report = analysis::sensitivity(&pool)?;
+    let priority_set = workspace
+        .get_active_priority_set_name()
+        .expect("sensitivity analysis requires an active priority set");
+    println!(
+        "'{}' ranks first under priority set '{priority_set}'",
+        report.leader
+    );
+
+    let mut rows = vec![vec![
+        "Criterion".to_string(),
+        "Priority".into(),
+        "Break-even below".into(),
+        "Break-even above".into(),
+    ]];
+    for criterion in &report.criteria {
+        rows.push(vec![
+            criterion.criterion.clone(),
+            format_number(criterion.priority),
+            format_break_even(&criterion.decrease),
+            format_break_even(&criterion.increase),
+        ]);
+    }
+    print!("{}", plain_table(&rows));
+
+    Ok(())
+}
+
+/// Formats a break-even as the priority and the project taking over, or `-` if the top-ranked
+/// project stays ahead no matter the priority.
+fn format_break_even(break_even: &Option<BreakEven>) -> String {
+    match break_even {
+        Some(break_even) => format!(
+            "{} ('{}')",
+            format_number(break_even.priority),
+            break_even.project
+        ),
+        None => "-".into(),
+    }
+}
+
+fn format_percent(probability: f64) -> String {
+    format!("{:.1}%", probability * 100.0)
+}
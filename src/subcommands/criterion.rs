@@ -0,0 +1,173 @@
+use anyhow::Result;
+use recision::{Criterion, Direction, Workspace};
+
+use super::write_workspace;
+use crate::config::Config;
+
+pub fn list(workspace: Workspace) -> Result<()> {
+    let criteria = workspace.get_criteria();
+
+    if criteria.is_empty() {
+        println!("No criteria in workspace");
+        return Ok(());
+    }
+
+    let max_id_str_len = format!("{}", criteria.len()).len();
+    let max_name_len = criteria
+        .iter()
+        .map(|c| c.get_name().chars().count())
+        .max()
+        .unwrap_or(0);
+    for (i, criterion) in criteria.iter().enumerate() {
+        let id_str = format!("{:>width$}", i + 1, width = max_id_str_len);
+        let name = criterion.get_name();
+        let priority = workspace.get_priority(name)?;
+        println!(
+            "{id_str} {name:<max_name_len$} {priority}{}",
+            format_details(criterion, workspace.get_budget_criterion() == Some(name))
+        )
+    }
+    Ok(())
+}
+
+/// Optional details of a criterion given on the command line.
+pub struct CriterionDetails {
+    pub direction: Option<Direction>,
+    pub min: Option<f64>,
+    pub max: Option<f64>,
+    pub unit: Option<String>,
+    pub description: Option<String>,
+    /// Designates the criterion as the one with the costs of the projects in portfolios.
+    pub budget_cost: bool,
+}
+
+pub fn add(
+    config: Config,
+    mut workspace: Workspace,
+    name: &str,
+    priority: Option<f64>,
+    details: CriterionDetails,
+) -> Result<()> {
+    let mut criterion = Criterion::new(name);
+    criterion.set_direction(details.direction.unwrap_or_default());
+    criterion.set_scale(details.min, details.max)?;
+    criterion.set_unit(details.unit);
+    criterion.set_description(details.description);
+
+    println!("Adding criterion '{name}'");
+    workspace.add_criterion(criterion)?;
+    if let Some(priority) = priority {
+        workspace.set_priority(name, priority)?;
+    }
+    if details.budget_cost {
+        workspace.set_budget_criterion(Some(name))?;
+    }
+
+    write_workspace(&config, &workspace)
+}
+
+pub fn remove(config: Config, mut workspace: Workspace, criteria: &[String]) -> Result<()> {
+    let names = criteria
+        .iter()
+        .map(|criterion| workspace.find_criterion(criterion))
+        .collect::<Result<Vec<_>>>()?;
+
+    for name in names {
+        println!("Removing criterion '{name}'");
+        workspace.remove_criterion(&name)?;
+    }
+
+    write_workspace(&config, &workspace)
+}
+
+pub fn reorder(config: Config, mut workspace: Workspace, criteria: &[String]) -> Result<()> {
+    let names = criteria
+        .iter()
+        .map(|criterion| workspace.find_criterion(criterion))
+        .collect::<Result<Vec<_>>>()?;
+
+    workspace.reorder_criteria(&names)?;
+
+    write_workspace(&config, &workspace)
+}
+
+pub fn update_priority(
+    config: Config,
+    mut workspace: Workspace,
+    criterion: &str,
+    priority: f64,
+) -> Result<()> {
+    let name = workspace.find_criterion(criterion)?;
+    println!("Setting priority of criterion '{name}' to {priority}");
+    workspace.set_priority(&name, priority)?;
+
+    write_workspace(&config, &workspace)
+}
+
+pub fn update(
+    config: Config,
+    mut workspace: Workspace,
+    criterion: &str,
+    details: CriterionDetails,
+) -> Result<()> {
+    let name = workspace.find_criterion(criterion)?;
+    println!("Updating criterion '{name}'");
+
+    if details.min.is_some() || details.max.is_some() {
+        let (min, max) = workspace
+            .get_criterion(&name)
+            .expect("criterion was resolved")
+            .get_scale();
+        workspace.set_criterion_scale(&name, details.min.or(min), details.max.or(max))?;
+    }
+
+    let criterion = workspace
+        .get_criterion(&name)
+        .expect("criterion was resolved");
+    if let Some(direction) = details.direction {
+        criterion.set_direction(direction);
+    }
+    if details.unit.is_some() {
+        criterion.set_unit(details.unit);
+    }
+    if details.description.is_some() {
+        criterion.set_description(details.description);
+    }
+    if details.budget_cost {
+        workspace.set_budget_criterion(Some(&name))?;
+    }
+
+    write_workspace(&config, &workspace)
+}
+
+/// Formats direction, scale, unit and description of a criterion, omitting defaults.
+fn format_details(criterion: &Criterion, budget_cost: bool) -> String {
+    let mut details = Vec::new();
+    if criterion.get_direction() == Direction::Cost {
+        details.push("cost".to_string());
+    }
+    if budget_cost {
+        details.push("budget cost".to_string());
+    }
+    match criterion.get_scale() {
+        (None, None) => {}
+        (min, max) => details.push(format!(
+            "{}..{}",
+            min.map(|min| min.to_string()).unwrap_or_default(),
+            max.map(|max| max.to_string()).unwrap_or_default()
+        )),
+    }
+    if let Some(unit) = criterion.get_unit() {
+        details.push(unit.to_string());
+    }
+
+    let mut formatted = String::new();
+    if !details.is_empty() {
+        formatted.push_str(&format!(" ({})", details.join(", ")));
+    }
+    if let Some(description) = criterion.get_description() {
+        formatted.push_str(&format!(" {description}"));
+    }
+
+    formatted
+}
@@ -0,0 +1,505 @@
+use std::{cmp::Ordering, collections::HashMap};
+
+use anyhow::Result;
+use recision::{
+    analysis,
+    dependency::{self, DependencyMode},
+    scoring::Method,
+    RecicionError, Workspace,
+};
+
+/// Characters used to draw the borders of the grid.
+struct BoxStyle {
+    horizontal: char,
+    vertical: char,
+    top: [char; 3],
+    middle: [char; 3],
+    bottom: [char; 3],
+    ellipsis: char,
+}
+
+const UNICODE: BoxStyle = BoxStyle {
+    horizontal: '─',
+    vertical: '│',
+    top: ['┌', '┬', '┐'],
+    middle: ['├', '┼', '┤'],
+    bottom: ['└', '┴', '┘'],
+    ellipsis: '…',
+};
+
+const ASCII: BoxStyle = BoxStyle {
+    horizontal: '-',
+    vertical: '|',
+    top: ['+', '+', '+'],
+    middle: ['+', '+', '+'],
+    bottom: ['+', '+', '+'],
+    ellipsis: '~',
+};
+
+/// Narrowest width a column is truncated to.
+const MIN_COLUMN_WIDTH: usize = 3;
+
+#[derive(Clone, Copy, PartialEq)]
+enum Alignment {
+    Left,
+    Right,
+}
+
+pub struct DisplayOptions {
+    /// Maximum width of the rendered grid; names are truncated to fit if set.
+    pub width: Option<usize>,
+    pub ascii: bool,
+    /// Scoring method that overrides the method of the workspace.
+    pub method: Option<Method>,
+    /// Shows the scores under every priority set instead of the grid of weights.
+    pub all_sets: bool,
+    /// Ranks the projects with regard to their dependencies.
+    pub dependencies: Option<DependencyMode>,
+    /// Includes archived projects in the ranking.
+    pub archived: bool,
+    /// Includes done and dropped projects in the ranking.
+    pub closed: bool,
+}
+
+pub fn display(workspace: Workspace, options: &DisplayOptions) -> Result<()> {
+    // IDs refer to the whole workspace, also when projects are left out
+    let ids: HashMap<String, usize> = workspace
+        .get_project_names()
+        .into_iter()
+        .enumerate()
+        .map(|(i, name)| (name, i + 1))
+        .collect();
+    let workspace = if options.archived {
+        workspace
+    } else {
+        workspace.without_archived()
+    };
+    let workspace = if options.closed {
+        workspace
+    } else {
+        workspace.without_closed()
+    };
+    let projects = workspace.get_project_names();
+    if projects.is_empty() {
+        println!("No projects in workspace");
+        return Ok(());
+    }
+
+    if options.all_sets {
+        return display_all_sets(workspace, &ids, options);
+    }
+
+    let criteria = workspace.get_criterion_names();
+    let priority_set = workspace
+        .get_active_priority_set_name()
+        .ok_or(RecicionError::new("no active priority set".into()))?
+        .to_string();
+    // projects violating a constraint are listed below the grid instead of being ranked
+    let vetoes = workspace.find_vetoes()?;
+    let mut workspace = workspace.without_vetoed()?;
+    let method = options.method.unwrap_or(workspace.get_scoring_method());
+    let scores = workspace.calculate_score_with(method.scoring_method())?;
+    // TOPSIS rankings are explained by the distances to the ideal and anti-ideal projects
+    let topsis = match method {
+        Method::Topsis => Some(workspace.calculate_topsis()?),
+        _ => None,
+    };
+    // uncertain weights widen the score to a range from the worst to the best case
+    let ranges = if workspace.has_uncertain_weights() {
+        Some(workspace.calculate_score_range_with(method.scoring_method())?)
+    } else {
+        None
+    };
+
+    let mut header = vec!["#".to_string(), "Project".to_string()];
+    header.extend(criteria.iter().cloned());
+    if topsis.is_some() {
+        header.extend(["D+".to_string(), "D-".to_string()]);
+    }
+    if ranges.is_some() {
+        header.extend(["Worst".to_string(), "Best".to_string()]);
+    }
+    header.push("Score".into());
+    let dependency_ranking = options
+        .dependencies
+        .map(|mode| dependency::rank(&workspace, &scores, mode))
+        .transpose()?;
+    let inherit = matches!(options.dependencies, Some(DependencyMode::Inherit { .. }));
+    if inherit {
+        header.push("Adjusted".into());
+    }
+
+    let mut priorities = vec![String::new(), format!("Priority ({priority_set})")];
+    for criterion in &criteria {
+        priorities.push(format_number(workspace.get_priority(criterion)?));
+    }
+    priorities.resize(header.len(), String::new());
+
+    let mut ranked = workspace.get_project_names();
+    match &dependency_ranking {
+        Some(ranking) => {
+            ranked.sort_by_key(|project| ranking.iter().position(|(name, _)| name == project))
+        }
+        None => ranked.sort_by(|a, b| scores[b].partial_cmp(&scores[a]).unwrap_or(Ordering::Equal)),
+    }
+
+    let mut rows = Vec::with_capacity(ranked.len());
+    for project in &ranked {
+        let mut row = vec![ids[project].to_string(), project.clone()];
+        for criterion in &criteria {
+            // unrated projects are scored with the worst weight, which a number would hide
+            row.push(match workspace.get_weight(project, criterion)? {
+                Some(weight) => weight.to_string(),
+                None => "-".into(),
+            });
+        }
+        if let Some(topsis) = &topsis {
+            row.push(format_number(topsis[project].ideal_distance));
+            row.push(format_number(topsis[project].anti_ideal_distance));
+        }
+        if let Some(ranges) = &ranges {
+            row.push(format_number(ranges[project].worst));
+            row.push(format_number(ranges[project].best));
+        }
+        row.push(format_number(scores[project]));
+        if let (true, Some(ranking)) = (inherit, &dependency_ranking) {
+            let (_, adjusted) = ranking
+                .iter()
+                .find(|(name, _)| name == project)
+                .expect("ranking contains all projects");
+            row.push(format_number(*adjusted));
+        }
+        rows.push(row);
+    }
+
+    let style = if options.ascii { &ASCII } else { &UNICODE };
+    print!(
+        "{}",
+        render(&header, &priorities, &rows, style, options.width)
+    );
+
+    if !vetoes.is_empty() {
+        println!("Vetoed projects:");
+        for project in &projects {
+            let reasons: Vec<_> = vetoes
+                .iter()
+                .filter(|veto| &veto.project == project)
+                .map(ToString::to_string)
+                .collect();
+            if !reasons.is_empty() {
+                println!("  {project} ({})", reasons.join("; "));
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// Displays the scores and ranks of the projects under every priority set, followed by the rank
+/// correlation of every pair of priority sets.
+fn display_all_sets(
+    workspace: Workspace,
+    ids: &HashMap<String, usize>,
+    options: &DisplayOptions,
+) -> Result<()> {
+    if workspace.get_priority_set_names().is_empty() {
+        return Err(RecicionError::new("no priority sets in workspace".into()).into());
+    }
+
+    let method = options.method.unwrap_or(workspace.get_scoring_method());
+    let comparison = analysis::compare_priority_sets(&workspace, method.scoring_method())?;
+    // sort by the active priority set, or by the first one if none is active
+    let sort_set = workspace
+        .get_active_priority_set_name()
+        .and_then(|name| comparison.priority_sets.iter().position(|set| set == name))
+        .unwrap_or(0);
+
+    let mut header = vec!["#".to_string(), "Project".to_string()];
+    header.extend(comparison.priority_sets.iter().cloned());
+    let mut labels = vec![String::new(), String::new()];
+    labels.resize(header.len(), "Score (rank)".into());
+
+    // projects vetoed by the sorting set come last
+    let mut order: Vec<_> = (0..comparison.projects.len()).collect();
+    order.sort_by_key(|project| {
+        let rank = comparison.ranks[*project][sort_set];
+        (rank.is_none(), rank)
+    });
+
+    let mut rows = Vec::with_capacity(order.len());
+    for project in order {
+        let name = &comparison.projects[project];
+        let ranks = &comparison.ranks[project];
+        let mut row = vec![ids[name].to_string(), name.clone()];
+        for set in 0..comparison.priority_sets.len() {
+            let (Some(rank), Some(score)) = (ranks[set], comparison.scores[project][set]) else {
+                row.push("vetoed".into());
+                continue;
+            };
+            let previous = set.checked_sub(1).and_then(|previous| ranks[previous]);
+            let change = match previous.map(|previous| previous as i64 - rank as i64) {
+                None | Some(0) => String::new(),
+                Some(change) => format!(", {change:+}"),
+            };
+            row.push(format!("{} (#{rank}{change})", format_number(score)));
+        }
+        rows.push(row);
+    }
+
+    let style = if options.ascii { &ASCII } else { &UNICODE };
+    print!("{}", render(&header, &labels, &rows, style, options.width));
+
+    if !comparison.correlations.is_empty() {
+        println!("Rank correlation (Kendall tau):");
+        for correlation in &comparison.correlations {
+            let tau = correlation
+                .kendall_tau
+                .map(format_number)
+                .unwrap_or("undefined".into());
+            println!("  {} / {}: {tau}", correlation.first, correlation.second);
+        }
+    }
+
+    Ok(())
+}
+
+/// Formats a number with at most two decimals and without trailing zeros.
+pub(super) fn format_number(number: f64) -> String {
+    let formatted = format!("{:.2}", number);
+    let trimmed = formatted.trim_end_matches('0').trim_end_matches('.');
+    match trimmed {
+        "-0" => "0".into(),
+        _ => trimmed.into(),
+    }
+}
+
+/// Renders a table without borders for reports, the first row being the header.
+///
+/// The first column is aligned left and all other columns right.
+pub(super) fn plain_table(rows: &[Vec<String>]) -> String {
+    let columns = rows.iter().map(Vec::len).max().unwrap_or(0);
+    let widths: Vec<_> = (0..columns)
+        .map(|column| {
+            rows.iter()
+                .filter_map(|row| row.get(column))
+                .map(|cell| cell.chars().count())
+                .max()
+                .unwrap_or(0)
+        })
+        .collect();
+
+    let mut output = String::new();
+    for row in rows {
+        let cells: Vec<_> = row
+            .iter()
+            .zip(&widths)
+            .enumerate()
+            .map(|(column, (cell, width))| match column {
+                0 => format!("{cell:<width$}"),
+                _ => format!("{cell:>width$}"),
+            })
+            .collect();
+        output.push_str(cells.join("  ").trim_end());
+        output.push('\n');
+    }
+
+    output
+}
+
+fn render(
+    header: &[String],
+    priorities: &[String],
+    rows: &[Vec<String>],
+    style: &BoxStyle,
+    max_width: Option<usize>,
+) -> String {
+    let alignments: Vec<_> = (0..header.len())
+        .map(|column| match column {
+            1 => Alignment::Left,
+            _ => Alignment::Right,
+        })
+        .collect();
+
+    let widths = column_widths(header, priorities, rows, max_width);
+
+    let mut output = String::new();
+    output.push_str(&rule(&widths, style.top, style));
+    output.push_str(&line(header, &widths, &alignments, style));
+    output.push_str(&rule(&widths, style.middle, style));
+    output.push_str(&line(priorities, &widths, &alignments, style));
+    output.push_str(&rule(&widths, style.middle, style));
+    for row in rows {
+        output.push_str(&line(row, &widths, &alignments, style));
+    }
+    output.push_str(&rule(&widths, style.bottom, style));
+
+    output
+}
+
+/// Calculates the width of every column, shrinking the text columns if the grid exceeds
+/// `max_width`.
+///
+/// Only the project names and the criterion headers are truncated; the widest of them is shrunk
+/// one character at a time so that numbers always stay readable.
+fn column_widths(
+    header: &[String],
+    priorities: &[String],
+    rows: &[Vec<String>],
+    max_width: Option<usize>,
+) -> Vec<usize> {
+    let width_of = |cell: &String| cell.chars().count();
+    let last = header.len() - 1;
+
+    let mut widths: Vec<_> = header.iter().map(width_of).collect();
+    let mut min_widths = vec![MIN_COLUMN_WIDTH; header.len()];
+    min_widths[0] = widths[0];
+    min_widths[last] = widths[last];
+    for row in std::iter::once(priorities).chain(rows.iter().map(Vec::as_slice)) {
+        for (column, cell) in row.iter().enumerate() {
+            widths[column] = widths[column].max(width_of(cell));
+            if column != 1 {
+                min_widths[column] = min_widths[column].max(width_of(cell));
+            }
+        }
+    }
+
+    let Some(max_width) = max_width else {
+        return widths;
+    };
+
+    // each column is padded by one space on both sides and followed by a border
+    let total_width = |widths: &[usize]| widths.iter().map(|w| w + 3).sum::<usize>() + 1;
+    while total_width(&widths) > max_width {
+        let widest = (1..last)
+            .filter(|column| widths[*column] > min_widths[*column])
+            .max_by_key(|column| widths[*column]);
+        match widest {
+            Some(column) => widths[column] -= 1,
+            None => break,
+        }
+    }
+
+    widths
+}
+
+fn rule(widths: &[usize], corners: [char; 3], style: &BoxStyle) -> String {
+    let segments: Vec<String> = widths
+        .iter()
+        .map(|width| style.horizontal.to_string().repeat(width + 2))
+        .collect();
+    format!(
+        "{}{}{}\n",
+        corners[0],
+        segments.join(&corners[1].to_string()),
+        corners[2]
+    )
+}
+
+fn line(cells: &[String], widths: &[usize], alignments: &[Alignment], style: &BoxStyle) -> String {
+    let mut output = String::new();
+    output.push(style.vertical);
+    for ((cell, width), alignment) in cells.iter().zip(widths).zip(alignments) {
+        let cell = truncate(cell, *width, style.ellipsis);
+        let padding = " ".repeat(width - cell.chars().count());
+        match alignment {
+            Alignment::Left => output.push_str(&format!(" {cell}{padding} ")),
+            Alignment::Right => output.push_str(&format!(" {padding}{cell} ")),
+        }
+        output.push(style.vertical);
+    }
+    output.push('\n');
+
+    output
+}
+
+fn truncate(text: &str, width: usize, ellipsis: char) -> String {
+    if text.chars().count() <= width {
+        return text.into();
+    }
+
+    let mut truncated: String = text.chars().take(width.saturating_sub(1)).collect();
+    truncated.push(ellipsis);
+    truncated
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn strings(cells: &[&str]) -> Vec<String> {
+        cells.iter().map(|cell| cell.to_string()).collect()
+    }
+
+    #[test]
+    fn test_plain_table() {
+        let rows = vec![
+            strings(&["Criterion", "Priority", "Note"]),
+            strings(&["Fun", "1.5", "x"]),
+            strings(&["Usefulness", "10", ""]),
+        ];
+
+        assert_eq!(
+            concat!(
+                "Criterion   Priority  Note\n",
+                "Fun              1.5     x\n",
+                "Usefulness        10\n",
+            ),
+            plain_table(&rows)
+        );
+    }
+
+    #[test]
+    fn test_format_number() {
+        assert_eq!("3", format_number(3.0));
+        assert_eq!("2.5", format_number(2.5));
+        assert_eq!("0.3", format_number(0.1 + 0.2));
+        assert_eq!("-1.33", format_number(-4.0 / 3.0));
+        assert_eq!("0", format_number(-0.001));
+    }
+
+    #[test]
+    fn test_truncate() {
+        assert_eq!("Useful", truncate("Useful", 6, '…'));
+        assert_eq!("Use…", truncate("Useful", 4, '…'));
+        assert_eq!("Use~", truncate("Useful", 4, '~'));
+    }
+
+    #[test]
+    fn test_render_ascii() {
+        let header = strings(&["#", "Project", "Fun", "Score"]);
+        let priorities = strings(&["", "Priority (Workday)", "2", ""]);
+        let rows = vec![
+            strings(&["2", "Beta", "3", "6"]),
+            strings(&["1", "Alpha", "-1", "-2"]),
+        ];
+
+        let rendered = render(&header, &priorities, &rows, &ASCII, None);
+
+        let expected = concat!(
+            "+---+--------------------+-----+-------+\n",
+            "| # | Project            | Fun | Score |\n",
+            "+---+--------------------+-----+-------+\n",
+            "|   | Priority (Workday) |   2 |       |\n",
+            "+---+--------------------+-----+-------+\n",
+            "| 2 | Beta               |   3 |     6 |\n",
+            "| 1 | Alpha              |  -1 |    -2 |\n",
+            "+---+--------------------+-----+-------+\n",
+        );
+        assert_eq!(expected, rendered);
+    }
+
+    #[test]
+    fn test_render_truncated() {
+        let header = strings(&["#", "Project", "Usefulness", "Score"]);
+        let priorities = strings(&["", "Priority (Workday)", "1", ""]);
+        let rows = vec![strings(&["1", "A rather long project name", "3", "3"])];
+
+        let rendered = render(&header, &priorities, &rows, &UNICODE, Some(30));
+
+        for line in rendered.lines() {
+            assert!(line.chars().count() <= 30, "line too wide: {line}");
+        }
+        assert!(rendered.contains('…'));
+        assert!(rendered.contains("Score"));
+    }
+}
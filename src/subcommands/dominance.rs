@@ -0,0 +1,36 @@
+use anyhow::Result;
+use recision::{analysis, Workspace};
+
+pub fn dominance(workspace: Workspace) -> Result<()> {
+    if workspace.get_project_names().is_empty() {
+        println!("No projects in workspace");
+        return Ok(());
+    }
+
+    let result = analysis::dominance(&workspace);
+
+    println!("Pareto-optimal projects:");
+    for project in &result.pareto_front {
+        println!("  {project}");
+    }
+
+    if result.dominated.is_empty() {
+        println!("No project is dominated by another");
+    } else {
+        println!("Dominated projects:");
+        for project in &result.dominated {
+            let dominated_by: Vec<_> = project
+                .dominated_by
+                .iter()
+                .map(|name| format!("'{name}'"))
+                .collect();
+            println!(
+                "  {} (dominated by {})",
+                project.name,
+                dominated_by.join(", ")
+            );
+        }
+    }
+
+    Ok(())
+}
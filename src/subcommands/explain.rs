@@ -0,0 +1,74 @@
+use anyhow::Result;
+use recision::{explain, Workspace};
+
+use super::display::{format_number, plain_table};
+
+pub fn explain(workspace: Workspace, project: &str) -> Result<()> {
+    let project = workspace.find_project(project)?;
+    let explanation = explain::explain(&workspace, &project)?;
+
+    println!(
+        "Score of '{project}' under priority set '{}' ({}): {}",
+        priority_set_name(&workspace),
+        explanation.method,
+        format_number(explanation.score)
+    );
+
+    let mut rows = vec![vec![
+        "Criterion".to_string(),
+        "Weight".into(),
+        "Priority".into(),
+        "Contribution".into(),
+        "Share".into(),
+    ]];
+    for contribution in &explanation.contributions {
+        rows.push(vec![
+            contribution.criterion.clone(),
+            contribution.weight.to_string(),
+            format_number(contribution.priority),
+            format_number(contribution.contribution),
+            format!("{:.1}%", contribution.share * 100.0),
+        ]);
+    }
+    print!("{}", plain_table(&rows));
+
+    Ok(())
+}
+
+pub fn compare(workspace: Workspace, first: &str, second: &str) -> Result<()> {
+    let first = workspace.find_project(first)?;
+    let second = workspace.find_project(second)?;
+    let comparison = explain::compare(&workspace, &first, &second)?;
+
+    println!(
+        "Under priority set '{}' ({}), '{first}' scores {} and '{second}' scores {}",
+        priority_set_name(&workspace),
+        comparison.first.method,
+        format_number(comparison.first.score),
+        format_number(comparison.second.score)
+    );
+
+    let mut rows = vec![vec![
+        "Criterion".to_string(),
+        first.clone(),
+        second.clone(),
+        "Difference".into(),
+    ]];
+    for difference in &comparison.differences {
+        rows.push(vec![
+            difference.criterion.clone(),
+            format_number(difference.first),
+            format_number(difference.second),
+            format_number(difference.difference),
+        ]);
+    }
+    print!("{}", plain_table(&rows));
+
+    Ok(())
+}
+
+fn priority_set_name(workspace: &Workspace) -> &str {
+    workspace
+        .get_active_priority_set_name()
+        .expect("scores are only explained under an active priority set")
+}
@@ -0,0 +1,44 @@
+use anyhow::Result;
+use recision::journal::{Action, Journal};
+
+use super::workspace_path;
+use crate::config::Config;
+
+pub fn undo(config: &Config) -> Result<()> {
+    let mut journal = Journal::read(&workspace_path(config)?)?;
+    let change = journal.undo(config.keeps_backup())?;
+    println!("Undid '{}'", change.operation);
+
+    Ok(())
+}
+
+pub fn redo(config: &Config) -> Result<()> {
+    let mut journal = Journal::read(&workspace_path(config)?)?;
+    let change = journal.redo(config.keeps_backup())?;
+    println!("Redid '{}'", change.operation);
+
+    Ok(())
+}
+
+pub fn history(config: &Config) -> Result<()> {
+    let journal = Journal::read(&workspace_path(config)?)?;
+    let entries = journal.get_entries();
+    if entries.is_empty() {
+        println!("No changes recorded");
+        return Ok(());
+    }
+
+    let max_id_str_len = format!("{}", entries.len()).len();
+    for (i, entry) in entries.iter().enumerate() {
+        let id_str = format!("{:>width$}", i + 1, width = max_id_str_len);
+        let description = match entry.action {
+            Action::Change if journal.is_undone(i) => format!("{} (undone)", entry.operation),
+            Action::Change => entry.operation.to_string(),
+            Action::Undo => format!("undo {}", entry.operation),
+            Action::Redo => format!("redo {}", entry.operation),
+        };
+        println!("{id_str} {} {description}", entry.at);
+    }
+
+    Ok(())
+}
@@ -0,0 +1,59 @@
+use anyhow::Result;
+use recision::{portfolio, RecicionError, Workspace};
+
+use super::display::format_number;
+
+pub fn portfolio(
+    workspace: Workspace,
+    budget: f64,
+    criterion: Option<&str>,
+    alternatives: usize,
+) -> Result<()> {
+    let criterion = match criterion {
+        Some(criterion) => workspace.find_criterion(criterion)?,
+        None => workspace
+            .get_budget_criterion()
+            .ok_or(RecicionError::new(
+                "no budget cost criterion, designate one with criterion update <CRITERION> --budget-cost or pass --criterion".into(),
+            ))?
+            .to_string(),
+    };
+
+    let portfolios = portfolio::select(&workspace, budget, &criterion, alternatives)?;
+    let (best, runner_ups) = portfolios
+        .split_first()
+        .expect("there is always a best portfolio");
+
+    println!(
+        "Best portfolio within a budget of {} ({criterion}):",
+        format_number(budget)
+    );
+    if best.projects.is_empty() {
+        println!("  No project fits into the budget");
+    }
+    for project in &best.projects {
+        println!("  {project}");
+    }
+    println!(
+        "Total score {}, cost {}, leftover budget {}",
+        format_number(best.score),
+        format_number(best.cost),
+        format_number(budget - best.cost)
+    );
+
+    if !runner_ups.is_empty() {
+        println!("Runner-ups:");
+        for (i, runner_up) in runner_ups.iter().enumerate() {
+            println!(
+                "  {}. score {}, cost {}, leftover {}: {}",
+                i + 2,
+                format_number(runner_up.score),
+                format_number(runner_up.cost),
+                format_number(budget - runner_up.cost),
+                runner_up.projects.join(", ")
+            );
+        }
+    }
+
+    Ok(())
+}
@@ -0,0 +1,246 @@
+use std::io::{self, BufRead, Write};
+
+use anyhow::{Context, Result};
+use recision::{
+    ahp::{self, Comparison, MAX_CONSISTENCY_RATIO},
+    Constraint, RecicionError, Workspace,
+};
+
+use super::{lock_workspace, read_workspace, write_workspace};
+use crate::config::Config;
+
+pub fn list(workspace: Workspace) -> Result<()> {
+    let priority_sets = workspace.get_priority_set_names();
+
+    if priority_sets.is_empty() {
+        println!("No priority sets in workspace");
+        return Ok(());
+    }
+
+    let active = workspace.get_active_priority_set_name();
+    let max_id_str_len = format!("{}", priority_sets.len()).len();
+    priority_sets.iter().enumerate().for_each(|(i, ps)| {
+        let id_str = format!("{:>width$}", i + 1, width = max_id_str_len);
+        let marker = if active == Some(ps.as_str()) {
+            '*'
+        } else {
+            ' '
+        };
+        println!("{marker} {id_str} {ps}");
+
+        let priority_set = workspace
+            .get_priority_set(ps)
+            .expect("priority set is in the workspace");
+        for criterion in workspace.get_criterion_names() {
+            if let Some(constraint) = priority_set.get_constraint(&criterion) {
+                println!(
+                    "  {:width$} {criterion} {constraint}",
+                    "",
+                    width = max_id_str_len
+                );
+            }
+        }
+    });
+    Ok(())
+}
+
+pub fn add(config: Config, mut workspace: Workspace, name: &str) -> Result<()> {
+    println!("Adding priority set '{name}'");
+    workspace.add_priority_set(name)?;
+
+    if workspace.get_active_priority_set_name().is_none() {
+        println!("Activating priority set '{name}'");
+        workspace.activate_priority_set(name)?;
+    }
+
+    write_workspace(&config, &workspace)
+}
+
+pub fn remove(config: Config, mut workspace: Workspace, priority_sets: &[String]) -> Result<()> {
+    let names = priority_sets
+        .iter()
+        .map(|ps| workspace.find_priority_set(ps))
+        .collect::<Result<Vec<_>>>()?;
+
+    for name in names {
+        println!("Removing priority set '{name}'");
+        workspace.remove_priority_set(&name)?;
+    }
+
+    write_workspace(&config, &workspace)
+}
+
+pub fn reorder(config: Config, mut workspace: Workspace, priority_sets: &[String]) -> Result<()> {
+    let names = priority_sets
+        .iter()
+        .map(|ps| workspace.find_priority_set(ps))
+        .collect::<Result<Vec<_>>>()?;
+
+    workspace.reorder_priority_sets(&names)?;
+
+    write_workspace(&config, &workspace)
+}
+
+pub fn activate(config: Config, mut workspace: Workspace, priority_set: &str) -> Result<()> {
+    let name = workspace.find_priority_set(priority_set)?;
+    println!("Activating priority set '{name}'");
+    workspace.activate_priority_set(&name)?;
+
+    write_workspace(&config, &workspace)
+}
+
+pub fn constrain(
+    config: Config,
+    mut workspace: Workspace,
+    priority_set: Option<&str>,
+    criterion: &str,
+    constraint: Constraint,
+) -> Result<()> {
+    let priority_set = resolve_or_active(&workspace, priority_set)?;
+    let criterion = workspace.find_criterion(criterion)?;
+
+    println!(
+        "Constraining criterion '{criterion}' to {constraint} in priority set '{priority_set}'"
+    );
+    workspace.set_constraint(&priority_set, &criterion, constraint)?;
+
+    write_workspace(&config, &workspace)
+}
+
+pub fn unconstrain(
+    config: Config,
+    mut workspace: Workspace,
+    priority_set: Option<&str>,
+    criterion: &str,
+) -> Result<()> {
+    let priority_set = resolve_or_active(&workspace, priority_set)?;
+    let criterion = workspace.find_criterion(criterion)?;
+
+    workspace.remove_constraint(&priority_set, &criterion)?;
+    println!("Removed constraint on criterion '{criterion}' from priority set '{priority_set}'");
+
+    write_workspace(&config, &workspace)
+}
+
+/// Asks for a pairwise comparison of every pair of criteria and derives the priorities of the
+/// priority set from them. Previous comparisons are offered as defaults.
+///
+/// The workspace is only locked once all comparisons are entered, and is read again then.
+pub fn compare(
+    config: Config,
+    workspace: Workspace,
+    priority_set: Option<&str>,
+    force: bool,
+    input: &mut impl BufRead,
+) -> Result<()> {
+    let name = resolve_or_active(&workspace, priority_set)?;
+    let previous = workspace
+        .get_priority_set(&name)
+        .expect("priority set was resolved")
+        .get_comparisons()
+        .to_vec();
+
+    let criteria = workspace.get_criterion_names();
+    if criteria.len() < 2 {
+        return Err(
+            RecicionError::new("at least two criteria are needed for comparisons".into()).into(),
+        );
+    }
+
+    println!("Comparing criteria for priority set '{name}' on a scale from 1/9 to 9");
+    let mut comparisons = Vec::new();
+    for (i, first) in criteria.iter().enumerate() {
+        for second in &criteria[i + 1..] {
+            let default = Comparison::value_of(&previous, first, second).unwrap_or(1.0);
+            let value = ask_comparison(input, first, second, default)?;
+            comparisons.push(Comparison::new(first, second, value)?);
+        }
+    }
+
+    let result = ahp::evaluate(&criteria, &comparisons)?;
+    println!("Consistency ratio: {:.3}", result.consistency_ratio);
+    if !result.is_consistent() {
+        if !force {
+            return Err(RecicionError::new(format!(
+                "comparisons are inconsistent (consistency ratio {:.3} > {}), rerun with --force to accept them",
+                result.consistency_ratio, MAX_CONSISTENCY_RATIO
+            ))
+            .into());
+        }
+        eprintln!(
+            "Warning: comparisons are inconsistent (consistency ratio {:.3} > {})",
+            result.consistency_ratio, MAX_CONSISTENCY_RATIO
+        );
+    }
+
+    let _lock = lock_workspace(&config)?;
+    let mut workspace = read_workspace(&config)?;
+    if workspace.get_criterion_names() != criteria {
+        return Err(RecicionError::new(
+            "criteria were changed while comparing them, rerun the comparison".into(),
+        )
+        .into());
+    }
+    workspace.set_comparisons(&name, comparisons)?;
+    for (criterion, priority) in criteria.iter().zip(&result.priorities) {
+        println!("{criterion}: {priority:.3}");
+    }
+
+    write_workspace(&config, &workspace)
+}
+
+/// Resolves the given priority set, or falls back to the active one.
+pub(super) fn resolve_or_active(
+    workspace: &Workspace,
+    priority_set: Option<&str>,
+) -> Result<String> {
+    match priority_set {
+        Some(priority_set) => workspace.find_priority_set(priority_set),
+        None => Ok(workspace
+            .get_active_priority_set_name()
+            .ok_or(RecicionError::new("no active priority set".into()))?
+            .to_string()),
+    }
+}
+
+/// Prompts until a valid value on the Saaty scale is entered; an empty line keeps the default.
+fn ask_comparison(
+    input: &mut impl BufRead,
+    first: &str,
+    second: &str,
+    default: f64,
+) -> Result<f64> {
+    loop {
+        print!(
+            "How much more important is '{first}' than '{second}'? [{}] ",
+            format_value(default)
+        );
+        io::stdout().flush()?;
+
+        let mut line = String::new();
+        if input
+            .read_line(&mut line)
+            .with_context(|| "reading comparison")?
+            == 0
+        {
+            return Err(RecicionError::new("comparisons were not completed".into()).into());
+        }
+        if line.trim().is_empty() {
+            return Ok(default);
+        }
+
+        match ahp::parse_value(&line).and_then(|value| Comparison::new(first, second, value)) {
+            Ok(comparison) => return Ok(comparison.value),
+            Err(error) => println!("Invalid comparison: {error}"),
+        }
+    }
+}
+
+/// Formats a value on the Saaty scale, showing values below one as fractions.
+fn format_value(value: f64) -> String {
+    if value < 1.0 {
+        format!("1/{}", (1.0 / value * 100.0).round() / 100.0)
+    } else {
+        format!("{}", (value * 100.0).round() / 100.0)
+    }
+}
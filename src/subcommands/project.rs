@@ -1,37 +1,263 @@
 use anyhow::Result;
-use recision::{Project, RecicionError, Workspace};
+use recision::{Project, RecicionError, Status, Workspace};
 
+use super::write_workspace;
 use crate::config::Config;
 
-pub fn list(workspace: Workspace) -> Result<()> {
-    let projects = workspace.get_project_names();
+pub fn list(workspace: Workspace, archived: bool) -> Result<()> {
+    let projects = workspace.get_projects();
 
     if projects.is_empty() {
         println!("No projects in workspace");
         return Ok(());
     }
+    if !archived && projects.iter().all(Project::is_archived) {
+        println!("No projects in the pool, list archived projects with --archived");
+        return Ok(());
+    }
 
+    // IDs count the archived projects, so that they stay the same when listing all projects
     let max_id_str_len = format!("{}", projects.len()).len();
-    projects.iter().enumerate().for_each(|(i, project)| {
-        let id_str = format!("{:>width$}", i + 1, width = max_id_str_len);
-        println!("{id_str} {project}")
-    });
+    projects
+        .iter()
+        .enumerate()
+        .filter(|(_, project)| archived || !project.is_archived())
+        .for_each(|(i, project)| {
+            let id_str = format!("{:>width$}", i + 1, width = max_id_str_len);
+            let mut tags = Vec::new();
+            if project.get_status() != Status::Idea {
+                tags.push(project.get_status().to_string());
+            }
+            if project.is_archived() {
+                tags.push("archived".into());
+            }
+            if tags.is_empty() {
+                println!("{id_str} {}", project.get_name());
+            } else {
+                println!("{id_str} {} ({})", project.get_name(), tags.join(", "));
+            }
+        });
     Ok(())
 }
 
 pub fn add(config: Config, mut workspace: Workspace, name: &str) -> Result<()> {
-    if config.get_workspace().is_none() {
-        return Err(RecicionError::new("no active workspace".into()).into());
-    }
-
     println!("Adding project '{name}'");
     workspace.add_project(Project::new(name))?;
-    workspace.write_to_file(
-        config
-            .get_workspace()
-            .clone()
-            .expect("active workspace present after early return"),
-    )?;
+
+    write_workspace(&config, &workspace)
+}
+
+pub fn remove(
+    config: Config,
+    mut workspace: Workspace,
+    projects: &[String],
+    dry_run: bool,
+) -> Result<()> {
+    let names = projects
+        .iter()
+        .map(|project| workspace.find_project(project))
+        .collect::<Result<Vec<_>>>()?;
+
+    for name in names {
+        println!("Removing project '{name}'");
+        workspace.remove_project(&name)?;
+    }
+
+    finish(config, workspace, dry_run)
+}
+
+pub fn reorder(
+    config: Config,
+    mut workspace: Workspace,
+    projects: &[String],
+    dry_run: bool,
+) -> Result<()> {
+    let names = projects
+        .iter()
+        .map(|project| workspace.find_project(project))
+        .collect::<Result<Vec<_>>>()?;
+
+    workspace.reorder_projects(&names)?;
+
+    finish(config, workspace, dry_run)
+}
+
+pub fn depend(
+    config: Config,
+    mut workspace: Workspace,
+    project: &str,
+    prerequisites: &[String],
+) -> Result<()> {
+    let project = workspace.find_project(project)?;
+    let names = prerequisites
+        .iter()
+        .map(|prerequisite| workspace.find_project(prerequisite))
+        .collect::<Result<Vec<_>>>()?;
+
+    for name in names {
+        workspace.add_dependency(&project, &name)?;
+        println!("Project '{project}' now depends on '{name}'");
+    }
+
+    write_workspace(&config, &workspace)
+}
+
+pub fn undepend(
+    config: Config,
+    mut workspace: Workspace,
+    project: &str,
+    prerequisites: &[String],
+) -> Result<()> {
+    let project = workspace.find_project(project)?;
+    let names = prerequisites
+        .iter()
+        .map(|prerequisite| workspace.find_project(prerequisite))
+        .collect::<Result<Vec<_>>>()?;
+
+    for name in names {
+        workspace.remove_dependency(&project, &name)?;
+        println!("Project '{project}' no longer depends on '{name}'");
+    }
+
+    write_workspace(&config, &workspace)
+}
+
+pub fn status(
+    config: Config,
+    mut workspace: Workspace,
+    project: &str,
+    status: Option<Status>,
+) -> Result<()> {
+    let name = workspace.find_project(project)?;
+
+    let Some(status) = status else {
+        let project = workspace
+            .get_project(&name)
+            .expect("resolved project is in the workspace");
+        println!("Status: {}", project.get_status());
+        if let Some(archived) = project.get_archived() {
+            println!("Archived: {archived}");
+        }
+        if !project.get_history().is_empty() {
+            println!("History:");
+            for change in project.get_history() {
+                println!("  {} {}", change.at, change.status);
+            }
+        }
+        return Ok(());
+    };
+
+    workspace.set_status(&name, status)?;
+    println!("Project '{name}' is now {status}");
+
+    write_workspace(&config, &workspace)
+}
+
+pub fn archive(config: Config, mut workspace: Workspace, projects: &[String]) -> Result<()> {
+    let names = projects
+        .iter()
+        .map(|project| workspace.find_project(project))
+        .collect::<Result<Vec<_>>>()?;
+
+    for name in names {
+        workspace.archive_project(&name)?;
+        println!("Archiving project '{name}'");
+    }
+
+    write_workspace(&config, &workspace)
+}
+
+pub fn restore(config: Config, mut workspace: Workspace, projects: &[String]) -> Result<()> {
+    let names = projects
+        .iter()
+        .map(|project| workspace.find_project(project))
+        .collect::<Result<Vec<_>>>()?;
+
+    for name in names {
+        workspace.restore_project(&name)?;
+        println!("Restoring project '{name}'");
+    }
+
+    write_workspace(&config, &workspace)
+}
+
+/// Characters used to draw the branches of a dependency tree.
+struct TreeStyle {
+    branch: &'static str,
+    last_branch: &'static str,
+    trunk: &'static str,
+}
+
+const UNICODE_TREE: TreeStyle = TreeStyle {
+    branch: "├── ",
+    last_branch: "└── ",
+    trunk: "│   ",
+};
+
+const ASCII_TREE: TreeStyle = TreeStyle {
+    branch: "|-- ",
+    last_branch: "`-- ",
+    trunk: "|   ",
+};
+
+pub fn deps(mut workspace: Workspace, project: Option<&str>, ascii: bool) -> Result<()> {
+    if workspace.get_project_names().is_empty() {
+        println!("No projects in workspace");
+        return Ok(());
+    }
+    if let Some(cycle) = workspace.find_dependency_cycle() {
+        return Err(RecicionError::new(format!(
+            "dependencies form the cycle {}",
+            cycle.join(" -> ")
+        ))
+        .into());
+    }
+
+    let roots = match project {
+        Some(project) => vec![workspace.find_project(project)?],
+        None => workspace
+            .get_project_names()
+            .into_iter()
+            .filter(|name| workspace.get_dependents(name).is_empty())
+            .collect(),
+    };
+
+    let style = if ascii { &ASCII_TREE } else { &UNICODE_TREE };
+    for root in roots {
+        println!("{root}");
+        print_prerequisites(&mut workspace, &root, "", style);
+    }
 
     Ok(())
 }
+
+fn print_prerequisites(workspace: &mut Workspace, project: &str, indent: &str, style: &TreeStyle) {
+    let prerequisites = workspace
+        .get_project(project)
+        .expect("dependencies refer to existing projects")
+        .get_dependencies()
+        .to_vec();
+
+    for (i, prerequisite) in prerequisites.iter().enumerate() {
+        let last = i + 1 == prerequisites.len();
+        let branch = if last {
+            style.last_branch
+        } else {
+            style.branch
+        };
+        println!("{indent}{branch}{prerequisite}");
+
+        let indent = format!("{indent}{}", if last { "    " } else { style.trunk });
+        print_prerequisites(workspace, prerequisite, &indent, style);
+    }
+}
+
+/// Writes the changed workspace, or only prints the resulting projects on a dry run.
+fn finish(config: Config, workspace: Workspace, dry_run: bool) -> Result<()> {
+    if dry_run {
+        println!("Dry run, the workspace is left unchanged. Resulting projects:");
+        return list(workspace, false);
+    }
+
+    write_workspace(&config, &workspace)
+}
@@ -0,0 +1,69 @@
+use anyhow::Result;
+use recision::{snapshot, Workspace};
+
+use super::{
+    display::{format_number, plain_table},
+    priority_set::resolve_or_active,
+    write_workspace,
+};
+use crate::config::Config;
+
+pub fn snapshot(config: Config, mut workspace: Workspace, label: &str) -> Result<()> {
+    workspace.take_snapshot(label)?;
+    println!("Recorded snapshot '{label}'");
+
+    write_workspace(&config, &workspace)
+}
+
+pub fn trend(workspace: Workspace, priority_set: Option<&str>, movers: usize) -> Result<()> {
+    let priority_set = resolve_or_active(&workspace, priority_set)?;
+    let trend = snapshot::trend(&workspace, &priority_set)?;
+    if trend.snapshots.is_empty() {
+        println!("No snapshots of priority set '{priority_set}'");
+        return Ok(());
+    }
+
+    println!("Scores (ranks) under priority set '{priority_set}':");
+    let mut rows = vec![vec!["Project".to_string()]];
+    rows[0].extend(trend.snapshots.iter().cloned());
+    rows[0].push("Change".into());
+    for (project, name) in trend.projects.iter().enumerate() {
+        let mut row = vec![name.clone()];
+        row.extend(
+            trend.scores[project]
+                .iter()
+                .zip(&trend.ranks[project])
+                .map(|(score, rank)| match (score, rank) {
+                    (Some(score), Some(rank)) => format!("{} (#{rank})", format_number(*score)),
+                    _ => "-".into(),
+                }),
+        );
+        row.push(match trend.rank_change(project) {
+            Some(0) | None => String::new(),
+            Some(change) => format!("{change:+}"),
+        });
+        rows.push(row);
+    }
+    print!("{}", plain_table(&rows));
+
+    let movers = trend.movers(movers);
+    if !movers.is_empty() {
+        println!("Biggest movers:");
+        for project in movers {
+            let ranks: Vec<_> = trend.ranks[project].iter().flatten().collect();
+            let change = trend
+                .rank_change(project)
+                .expect("movers changed their rank");
+            let direction = if change > 0 { "up" } else { "down" };
+            println!(
+                "  {} {direction} {} (#{} -> #{})",
+                trend.projects[project],
+                change.abs(),
+                ranks[0],
+                ranks[ranks.len() - 1]
+            );
+        }
+    }
+
+    Ok(())
+}
@@ -0,0 +1,36 @@
+use anyhow::Result;
+use recision::{Weight, Workspace};
+
+use super::write_workspace;
+use crate::config::Config;
+
+pub fn get(mut workspace: Workspace, project: &str, criterion: &str) -> Result<()> {
+    let project = workspace.find_project(project)?;
+    let criterion = workspace.find_criterion(criterion)?;
+
+    match workspace.get_weight(&project, &criterion)? {
+        Some(weight) => println!("{weight}"),
+        None => println!(
+            "unrated, scored as {}",
+            workspace.get_effective_weight(&project, &criterion)?
+        ),
+    }
+
+    Ok(())
+}
+
+pub fn set(
+    config: Config,
+    mut workspace: Workspace,
+    project: &str,
+    criterion: &str,
+    weight: Weight,
+) -> Result<()> {
+    let project = workspace.find_project(project)?;
+    let criterion = workspace.find_criterion(criterion)?;
+
+    println!("Setting weight of project '{project}' for criterion '{criterion}' to {weight}");
+    workspace.set_weight(&project, &criterion, weight)?;
+
+    write_workspace(&config, &workspace)
+}
@@ -1,24 +1,12 @@
-use std::{error::Error, fmt, path::PathBuf};
+use std::{fs, path::PathBuf};
 
-use anyhow::{Ok, Result};
+use anyhow::{Context, Ok, Result};
 use path_absolutize::Absolutize;
-use recision::{RecicionError, Workspace};
+use recision::{migration, RecicionError, Workspace};
 
+use super::{lock_workspace_unless, read_workspace, workspace_path, write_workspace};
 use crate::config::Config;
 
-#[derive(Debug)]
-pub struct WorkspaceError {
-    message: String,
-}
-
-impl fmt::Display for WorkspaceError {
-    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        write!(f, "{}", self.message)
-    }
-}
-
-impl Error for WorkspaceError {}
-
 pub fn new(path: PathBuf, config: &mut Config) -> Result<()> {
     if path.exists() {
         return Err(
@@ -27,16 +15,20 @@ pub fn new(path: PathBuf, config: &mut Config) -> Result<()> {
     }
 
     let workspace = Workspace::new();
-    workspace.write_to_file(path.clone())?;
+    workspace.write_to_file(path.clone(), false)?;
     config.set_workspace(Some(path))?;
 
     Ok(())
 }
 
 pub fn activate(path: PathBuf, config: &mut Config) -> Result<()> {
-    let _ = Workspace::read_from_file(path.absolutize().unwrap().to_path_buf())?;
+    let path = path
+        .absolutize()
+        .with_context(|| format!("resolving workspace path {}", path.display()))?
+        .to_path_buf();
+    let _ = Workspace::read_from_file(path.clone())?;
 
-    config.set_workspace(Some(path.absolutize().unwrap().to_path_buf()))?;
+    config.set_workspace(Some(path))?;
 
     Ok(())
 }
@@ -49,12 +41,52 @@ pub fn deactivate(config: &mut Config) -> Result<()> {
 
 pub fn status(config: &mut Config) -> Result<()> {
     match config.get_workspace() {
-        Some(path) => println!(
-            "Active workspace: {}",
-            path.absolutize().unwrap().to_str().unwrap()
-        ),
+        Some(_) => println!("Active workspace: {}", workspace_path(config)?.display()),
         None => println!("No active workspace"),
     }
 
     Ok(())
 }
+
+pub fn migrate(config: &Config, check: bool) -> Result<()> {
+    let path = workspace_path(config)?;
+    let _lock = lock_workspace_unless(config, check)?;
+
+    let toml_string = fs::read_to_string(path).with_context(|| "reading workspace from file")?;
+    let document: toml::Table =
+        toml::from_str(&toml_string).with_context(|| "parsing contents of workspace file")?;
+    let version = migration::schema_version(&document)?;
+    let pending = migration::pending(&document)?;
+
+    if pending.is_empty() {
+        println!("Workspace is up to date with schema version {version}");
+        return Ok(());
+    }
+
+    println!(
+        "Workspace has schema version {version}, the current schema version is {}",
+        migration::SCHEMA_VERSION
+    );
+    for migration in &pending {
+        println!(
+            "  {} -> {}: {}",
+            migration.from,
+            migration.from + 1,
+            migration.description
+        );
+    }
+
+    if check {
+        println!("Check only, the workspace is left unchanged");
+        return Ok(());
+    }
+
+    let workspace = read_workspace(config)?;
+    write_workspace(config, &workspace)?;
+    println!(
+        "Upgraded workspace to schema version {}",
+        workspace.get_schema_version()
+    );
+
+    Ok(())
+}
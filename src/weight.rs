@@ -0,0 +1,243 @@
+//! Weights of projects on criteria, which may be exact or uncertain.
+
+use std::{
+    fmt::{self, Display, Formatter},
+    str::FromStr,
+};
+
+use anyhow::Result;
+use serde::{Deserialize, Serialize, Serializer};
+
+use crate::RecicionError;
+
+/// The weight of a project on a criterion.
+///
+/// In the workspace file an exact weight is a plain number, while uncertain weights are tables
+/// with the keys `min` and `max`, and additionally `likely` for three-point estimates.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum Weight {
+    Exact(#[serde(serialize_with = "serialize_number")] f64),
+    /// Triangular estimate with the most likely value somewhere between the bounds.
+    Triangular {
+        #[serde(serialize_with = "serialize_number")]
+        min: f64,
+        #[serde(serialize_with = "serialize_number")]
+        likely: f64,
+        #[serde(serialize_with = "serialize_number")]
+        max: f64,
+    },
+    /// Any value between the bounds is equally likely.
+    Range {
+        #[serde(serialize_with = "serialize_number")]
+        min: f64,
+        #[serde(serialize_with = "serialize_number")]
+        max: f64,
+    },
+}
+
+impl Weight {
+    pub fn range(min: f64, max: f64) -> Result<Self> {
+        Weight::Range { min, max }.validated()
+    }
+
+    pub fn triangular(min: f64, likely: f64, max: f64) -> Result<Self> {
+        Weight::Triangular { min, likely, max }.validated()
+    }
+
+    /// Checks that the weight is a finite number and that the bounds of an uncertain weight are
+    /// in order.
+    pub fn validated(self) -> Result<Self> {
+        let finite = match self {
+            Weight::Exact(value) => value.is_finite(),
+            Weight::Range { min, max } => min.is_finite() && max.is_finite(),
+            Weight::Triangular { min, likely, max } => {
+                min.is_finite() && likely.is_finite() && max.is_finite()
+            }
+        };
+        if !finite {
+            return Err(
+                RecicionError::new(format!("weight {} is not a finite number", self)).into(),
+            );
+        }
+
+        let ordered = match self {
+            Weight::Exact(_) => true,
+            Weight::Range { min, max } => min <= max,
+            Weight::Triangular { min, likely, max } => min <= likely && likely <= max,
+        };
+
+        if !ordered {
+            return Err(
+                RecicionError::new(format!("bounds of weight {} are not in order", self)).into(),
+            );
+        }
+
+        Ok(self)
+    }
+
+    pub fn min(&self) -> f64 {
+        match self {
+            Weight::Exact(value) => *value,
+            Weight::Range { min, .. } | Weight::Triangular { min, .. } => *min,
+        }
+    }
+
+    pub fn max(&self) -> f64 {
+        match self {
+            Weight::Exact(value) => *value,
+            Weight::Range { max, .. } | Weight::Triangular { max, .. } => *max,
+        }
+    }
+
+    /// Mean value of the weight.
+    pub fn expected(&self) -> f64 {
+        match self {
+            Weight::Exact(value) => *value,
+            Weight::Range { min, max } => (min + max) / 2.0,
+            Weight::Triangular { min, likely, max } => (min + likely + max) / 3.0,
+        }
+    }
+
+    pub fn is_exact(&self) -> bool {
+        matches!(self, Weight::Exact(_))
+    }
+}
+
+impl Default for Weight {
+    fn default() -> Self {
+        Weight::Exact(0.0)
+    }
+}
+
+impl From<f64> for Weight {
+    fn from(value: f64) -> Self {
+        Weight::Exact(value)
+    }
+}
+
+impl From<i32> for Weight {
+    fn from(value: i32) -> Self {
+        Weight::Exact(value as f64)
+    }
+}
+
+impl Display for Weight {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            Weight::Exact(value) => write!(f, "{}", value),
+            Weight::Range { min, max } => write!(f, "{}..{}", min, max),
+            Weight::Triangular { min, likely, max } => write!(f, "{}..{}..{}", min, likely, max),
+        }
+    }
+}
+
+/// Parses `2.5` as an exact weight, `1..4` as a range and `1..2..5` as a triangular estimate.
+impl FromStr for Weight {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        let values = s
+            .split("..")
+            .map(|value| {
+                value
+                    .trim()
+                    .parse::<f64>()
+                    .map_err(|_| RecicionError::new(format!("invalid weight {}", s)).into())
+            })
+            .collect::<Result<Vec<_>>>()?;
+
+        match values.as_slice() {
+            [value] => Weight::Exact(*value).validated(),
+            [min, max] => Weight::range(*min, *max),
+            [min, likely, max] => Weight::triangular(*min, *likely, *max),
+            _ => Err(RecicionError::new(format!("invalid weight {}", s)).into()),
+        }
+    }
+}
+
+/// Writes whole numbers as integers to keep workspace files readable by older versions.
+fn serialize_number<S: Serializer>(value: &f64, serializer: S) -> Result<S::Ok, S::Error> {
+    if value.fract() == 0.0 && value.abs() <= i32::MAX as f64 {
+        serializer.serialize_i64(*value as i64)
+    } else {
+        serializer.serialize_f64(*value)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_weight() {
+        assert_eq!(Weight::Exact(2.5), "2.5".parse().unwrap());
+        assert_eq!(Weight::Exact(-1.0), "-1".parse().unwrap());
+        assert_eq!(Weight::range(1.0, 4.0).unwrap(), "1..4".parse().unwrap());
+        assert_eq!(
+            Weight::triangular(1.0, 2.0, 5.0).unwrap(),
+            "1 .. 2 .. 5".parse().unwrap()
+        );
+        assert!("4..1".parse::<Weight>().is_err());
+        assert!("1..5..2".parse::<Weight>().is_err());
+        assert!("1..2..3..4".parse::<Weight>().is_err());
+        assert!("high".parse::<Weight>().is_err());
+    }
+
+    #[test]
+    fn test_non_finite_weight() {
+        for weight in [
+            "nan",
+            "inf",
+            "-inf",
+            "NaN..1",
+            "1..inf",
+            "0..nan..1",
+            "-inf..0..1",
+        ] {
+            assert!(weight.parse::<Weight>().is_err(), "{weight} was accepted");
+        }
+        assert!(Weight::Exact(f64::NAN).validated().is_err());
+        assert!(Weight::range(f64::NAN, f64::NAN).is_err());
+        assert!(Weight::triangular(0.0, 1.0, f64::INFINITY).is_err());
+    }
+
+    #[test]
+    fn test_display_round_trip() {
+        for weight in ["3", "2.5", "1..4", "-1..0.5..2"] {
+            assert_eq!(weight, weight.parse::<Weight>().unwrap().to_string());
+        }
+    }
+
+    #[test]
+    fn test_expected_weight() {
+        assert_eq!(3.0, Weight::Exact(3.0).expected());
+        assert_eq!(2.5, Weight::range(1.0, 4.0).unwrap().expected());
+        assert_eq!(3.0, Weight::triangular(1.0, 2.0, 6.0).unwrap().expected());
+    }
+
+    #[derive(Debug, PartialEq, Serialize, Deserialize)]
+    struct Weights {
+        fun: Weight,
+        useful: Weight,
+        cheap: Weight,
+    }
+
+    #[test]
+    fn test_toml_format() {
+        let toml_string = concat!(
+            "fun = 3\n",
+            "useful = { min = 1, max = 4.5 }\n",
+            "cheap = { min = 1, likely = 2, max = 5 }\n"
+        );
+        let weights: Weights = toml::from_str(toml_string).unwrap();
+
+        assert_eq!(Weight::Exact(3.0), weights.fun);
+        assert_eq!(Weight::range(1.0, 4.5).unwrap(), weights.useful);
+        assert_eq!(Weight::triangular(1.0, 2.0, 5.0).unwrap(), weights.cheap);
+
+        let serialized = toml::to_string(&weights).unwrap();
+        assert!(serialized.contains("fun = 3\n"));
+        assert_eq!(weights, toml::from_str(&serialized).unwrap());
+    }
+}
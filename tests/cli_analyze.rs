@@ -0,0 +1,174 @@
+use std::fs;
+
+use anyhow::{Ok, Result};
+use common::{build_command, create_test_config_dir, TEST_CONFIG_DIR};
+use predicates::str::{contains, diff, is_match};
+use temp_env::with_vars;
+use tempfile::NamedTempFile;
+
+mod common;
+
+#[test]
+fn test_analyze_robustness() -> Result<()> {
+    let temp_dir = create_test_config_dir()?;
+    let temp_dir_path = temp_dir.path();
+    let workspace_file = NamedTempFile::new()?;
+    fs::remove_file(workspace_file.path())?;
+    with_vars(
+        [(
+            TEST_CONFIG_DIR,
+            Some(temp_dir_path.as_os_str().to_str().unwrap()),
+        )],
+        || {
+            build_command()?
+                .arg("workspace")
+                .arg("new")
+                .arg(workspace_file.path())
+                .assert()
+                .success();
+
+            for project in ["Safe bet", "Gamble"] {
+                build_command()?
+                    .arg("project")
+                    .arg("add")
+                    .arg(project)
+                    .assert()
+                    .success();
+            }
+
+            build_command()?
+                .arg("criterion")
+                .arg("add")
+                .arg("Fun")
+                .assert()
+                .success();
+
+            build_command()?
+                .arg("priority-set")
+                .arg("add")
+                .arg("Workday")
+                .assert()
+                .success();
+
+            build_command()?
+                .args(["weight", "set", "Safe bet", "Fun", "5"])
+                .assert()
+                .success();
+
+            build_command()?
+                .args(["weight", "set", "Gamble", "Fun", "0..20"])
+                .assert()
+                .success();
+
+            let output = build_command()?
+                .args(["analyze", "robustness", "--seed", "42"])
+                .assert()
+                .success()
+                .stdout(is_match(r"Project\s+First\s+Mean rank\s+#1\s+#2")?)
+                .stdout(is_match(
+                    r"'Gamble' ranks first in 7\d\.\d% of 1000 iterations \(seed 42\)",
+                )?)
+                .get_output()
+                .stdout
+                .clone();
+
+            build_command()?
+                .args(["analyze", "robustness", "--seed", "42"])
+                .assert()
+                .success()
+                .stdout(String::from_utf8(output)?);
+
+            build_command()?
+                .args(["analyze", "robustness", "-n", "0"])
+                .assert()
+                .failure()
+                .stderr(contains("at least one iteration"));
+
+            build_command()?
+                .args(["priority-set", "constrain", "Fun", "--min", "100"])
+                .assert()
+                .success();
+
+            for analysis in ["robustness", "sensitivity"] {
+                build_command()?
+                    .args(["analyze", analysis])
+                    .assert()
+                    .success()
+                    .stdout(diff("All projects are vetoed\n"));
+            }
+
+            Ok(())
+        },
+    )?;
+
+    Ok(())
+}
+
+#[test]
+fn test_analyze_sensitivity() -> Result<()> {
+    let temp_dir = create_test_config_dir()?;
+    let temp_dir_path = temp_dir.path();
+    let workspace_file = NamedTempFile::new()?;
+    fs::remove_file(workspace_file.path())?;
+    with_vars(
+        [(
+            TEST_CONFIG_DIR,
+            Some(temp_dir_path.as_os_str().to_str().unwrap()),
+        )],
+        || {
+            build_command()?
+                .arg("workspace")
+                .arg("new")
+                .arg(workspace_file.path())
+                .assert()
+                .success();
+
+            for project in ["Alpha", "Beta"] {
+                build_command()?
+                    .arg("project")
+                    .arg("add")
+                    .arg(project)
+                    .assert()
+                    .success();
+            }
+
+            for criterion in ["Fun", "Useful"] {
+                build_command()?
+                    .arg("criterion")
+                    .arg("add")
+                    .arg(criterion)
+                    .assert()
+                    .success();
+            }
+
+            build_command()?
+                .arg("priority-set")
+                .arg("add")
+                .arg("Workday")
+                .assert()
+                .success();
+
+            build_command()?
+                .args(["weight", "set", "Alpha", "Fun", "4"])
+                .assert()
+                .success();
+
+            build_command()?
+                .args(["weight", "set", "Beta", "Useful", "2"])
+                .assert()
+                .success();
+
+            build_command()?
+                .args(["analyze", "sensitivity"])
+                .assert()
+                .success()
+                .stdout(contains("'Alpha' ranks first under priority set 'Workday'"))
+                .stdout(is_match(r"Fun\s+1\s+0.5 \('Beta'\)\s+-")?)
+                .stdout(is_match(r"Useful\s+1\s+-\s+2 \('Beta'\)")?);
+
+            Ok(())
+        },
+    )?;
+
+    Ok(())
+}
@@ -0,0 +1,351 @@
+use std::fs;
+
+use anyhow::{Ok, Result};
+use common::{build_command, create_test_config_dir, TEST_CONFIG_DIR};
+use mockall::PredicateBooleanExt;
+use predicates::str::{contains, is_match};
+use temp_env::with_vars;
+use tempfile::NamedTempFile;
+
+mod common;
+
+#[test]
+fn test_add_criterion() -> Result<()> {
+    let temp_dir = create_test_config_dir()?;
+    let temp_dir_path = temp_dir.path();
+    let workspace_file = NamedTempFile::new()?;
+    fs::remove_file(workspace_file.path())?;
+    with_vars(
+        [(
+            TEST_CONFIG_DIR,
+            Some(temp_dir_path.as_os_str().to_str().unwrap()),
+        )],
+        || {
+            build_command()?
+                .arg("workspace")
+                .arg("new")
+                .arg(workspace_file.path())
+                .assert()
+                .success();
+
+            build_command()?
+                .arg("criterion")
+                .arg("add")
+                .arg("Fun")
+                .assert()
+                .success();
+
+            build_command()?
+                .arg("criterion")
+                .arg("add")
+                .arg("Useful")
+                .assert()
+                .success();
+
+            build_command()?
+                .arg("criterion")
+                .arg("list")
+                .assert()
+                .success()
+                .stdout(is_match("1 Fun +1\n2 Useful +1\n")?);
+
+            Ok(())
+        },
+    )?;
+
+    Ok(())
+}
+
+#[test]
+fn test_add_criterion_twice() -> Result<()> {
+    let temp_dir = create_test_config_dir()?;
+    let temp_dir_path = temp_dir.path();
+    let workspace_file = NamedTempFile::new()?;
+    fs::remove_file(workspace_file.path())?;
+    with_vars(
+        [(
+            TEST_CONFIG_DIR,
+            Some(temp_dir_path.as_os_str().to_str().unwrap()),
+        )],
+        || {
+            build_command()?
+                .arg("workspace")
+                .arg("new")
+                .arg(workspace_file.path())
+                .assert()
+                .success();
+
+            build_command()?
+                .arg("criterion")
+                .arg("add")
+                .arg("Fun")
+                .assert()
+                .success();
+
+            build_command()?
+                .arg("criterion")
+                .arg("add")
+                .arg("Fun")
+                .assert()
+                .failure();
+
+            build_command()?
+                .arg("criterion")
+                .arg("add")
+                .arg("3")
+                .assert()
+                .failure();
+
+            Ok(())
+        },
+    )?;
+
+    Ok(())
+}
+
+#[test]
+fn test_remove_criterion() -> Result<()> {
+    let temp_dir = create_test_config_dir()?;
+    let temp_dir_path = temp_dir.path();
+    let workspace_file = NamedTempFile::new()?;
+    fs::remove_file(workspace_file.path())?;
+    with_vars(
+        [(
+            TEST_CONFIG_DIR,
+            Some(temp_dir_path.as_os_str().to_str().unwrap()),
+        )],
+        || {
+            build_command()?
+                .arg("workspace")
+                .arg("new")
+                .arg(workspace_file.path())
+                .assert()
+                .success();
+
+            for name in ["Fun", "Useful", "Cheap"] {
+                build_command()?
+                    .arg("criterion")
+                    .arg("add")
+                    .arg(name)
+                    .assert()
+                    .success();
+            }
+
+            build_command()?
+                .arg("criterion")
+                .arg("remove")
+                .arg("Fun")
+                .arg("3")
+                .assert()
+                .success();
+
+            build_command()?
+                .arg("criterion")
+                .arg("list")
+                .assert()
+                .stdout(contains("Useful"))
+                .stdout(contains("Fun").not().and(contains("Cheap").not()));
+
+            build_command()?
+                .arg("criterion")
+                .arg("remove")
+                .arg("Funn")
+                .assert()
+                .failure();
+
+            Ok(())
+        },
+    )?;
+
+    Ok(())
+}
+
+#[test]
+fn test_reorder_criteria() -> Result<()> {
+    let temp_dir = create_test_config_dir()?;
+    let temp_dir_path = temp_dir.path();
+    let workspace_file = NamedTempFile::new()?;
+    fs::remove_file(workspace_file.path())?;
+    with_vars(
+        [(
+            TEST_CONFIG_DIR,
+            Some(temp_dir_path.as_os_str().to_str().unwrap()),
+        )],
+        || {
+            build_command()?
+                .arg("workspace")
+                .arg("new")
+                .arg(workspace_file.path())
+                .assert()
+                .success();
+
+            for name in ["Fun", "Useful", "Cheap"] {
+                build_command()?
+                    .arg("criterion")
+                    .arg("add")
+                    .arg(name)
+                    .assert()
+                    .success();
+            }
+
+            build_command()?
+                .arg("criterion")
+                .arg("reorder")
+                .arg("Cheap")
+                .arg("2")
+                .assert()
+                .success();
+
+            build_command()?
+                .arg("criterion")
+                .arg("list")
+                .assert()
+                .stdout(is_match("1 Cheap +1\n2 Useful +1\n3 Fun +1\n")?);
+
+            Ok(())
+        },
+    )?;
+
+    Ok(())
+}
+
+#[test]
+fn test_update_priority_without_priority_set() -> Result<()> {
+    let temp_dir = create_test_config_dir()?;
+    let temp_dir_path = temp_dir.path();
+    let workspace_file = NamedTempFile::new()?;
+    fs::remove_file(workspace_file.path())?;
+    with_vars(
+        [(
+            TEST_CONFIG_DIR,
+            Some(temp_dir_path.as_os_str().to_str().unwrap()),
+        )],
+        || {
+            build_command()?
+                .arg("workspace")
+                .arg("new")
+                .arg(workspace_file.path())
+                .assert()
+                .success();
+
+            build_command()?
+                .arg("criterion")
+                .arg("add")
+                .arg("Fun")
+                .assert()
+                .success();
+
+            build_command()?
+                .arg("criterion")
+                .arg("update-priority")
+                .arg("Fun")
+                .arg("2")
+                .assert()
+                .failure()
+                .stderr(contains("no active priority set"));
+
+            Ok(())
+        },
+    )?;
+
+    Ok(())
+}
+
+#[test]
+fn test_criterion_details() -> Result<()> {
+    let temp_dir = create_test_config_dir()?;
+    let temp_dir_path = temp_dir.path();
+    let workspace_file = NamedTempFile::new()?;
+    fs::remove_file(workspace_file.path())?;
+    with_vars(
+        [(
+            TEST_CONFIG_DIR,
+            Some(temp_dir_path.as_os_str().to_str().unwrap()),
+        )],
+        || {
+            build_command()?
+                .arg("workspace")
+                .arg("new")
+                .arg(workspace_file.path())
+                .assert()
+                .success();
+
+            build_command()?
+                .arg("project")
+                .arg("add")
+                .arg("project 1")
+                .assert()
+                .success();
+
+            build_command()?
+                .arg("criterion")
+                .arg("add")
+                .arg("Effort")
+                .arg("--cost")
+                .arg("--min")
+                .arg("0")
+                .arg("--max")
+                .arg("5")
+                .arg("--unit")
+                .arg("PW")
+                .assert()
+                .success();
+
+            build_command()?
+                .arg("criterion")
+                .arg("list")
+                .assert()
+                .stdout(contains("1 Effort 1 (cost, 0..5, PW)"));
+
+            build_command()?
+                .arg("weight")
+                .arg("set")
+                .arg("project 1")
+                .arg("Effort")
+                .arg("6")
+                .assert()
+                .failure()
+                .stderr(contains("outside of the scale"));
+
+            build_command()?
+                .arg("criterion")
+                .arg("update")
+                .arg("Effort")
+                .arg("--max")
+                .arg("10")
+                .arg("--benefit")
+                .arg("--description")
+                .arg("Hours of work")
+                .assert()
+                .success();
+
+            build_command()?
+                .arg("weight")
+                .arg("set")
+                .arg("project 1")
+                .arg("Effort")
+                .arg("6")
+                .assert()
+                .success();
+
+            build_command()?
+                .arg("criterion")
+                .arg("list")
+                .assert()
+                .stdout(contains("1 Effort 1 (0..10, PW) Hours of work"));
+
+            build_command()?
+                .arg("criterion")
+                .arg("update")
+                .arg("1")
+                .arg("--max")
+                .arg("5")
+                .assert()
+                .failure();
+
+            Ok(())
+        },
+    )?;
+
+    Ok(())
+}
@@ -0,0 +1,446 @@
+use std::fs;
+
+use anyhow::{Ok, Result};
+use common::{build_command, create_test_config_dir, TEST_CONFIG_DIR};
+use mockall::PredicateBooleanExt;
+use predicates::str::{contains, is_match};
+use temp_env::with_vars;
+use tempfile::NamedTempFile;
+
+mod common;
+
+#[test]
+fn test_display_sorted_by_score() -> Result<()> {
+    let temp_dir = create_test_config_dir()?;
+    let temp_dir_path = temp_dir.path();
+    let workspace_file = NamedTempFile::new()?;
+    fs::remove_file(workspace_file.path())?;
+    with_vars(
+        [(
+            TEST_CONFIG_DIR,
+            Some(temp_dir_path.as_os_str().to_str().unwrap()),
+        )],
+        || {
+            build_command()?
+                .arg("workspace")
+                .arg("new")
+                .arg(workspace_file.path())
+                .assert()
+                .success();
+
+            for project in ["project 1", "project 2"] {
+                build_command()?
+                    .arg("project")
+                    .arg("add")
+                    .arg(project)
+                    .assert()
+                    .success();
+            }
+
+            build_command()?
+                .arg("criterion")
+                .arg("add")
+                .arg("Fun")
+                .assert()
+                .success();
+
+            build_command()?
+                .arg("display")
+                .assert()
+                .failure()
+                .stderr(contains("no active priority set"));
+
+            build_command()?
+                .arg("priority-set")
+                .arg("add")
+                .arg("Workday")
+                .assert()
+                .success();
+
+            build_command()?
+                .arg("criterion")
+                .arg("update-priority")
+                .arg("Fun")
+                .arg("1.5")
+                .assert()
+                .success();
+
+            build_command()?
+                .arg("weight")
+                .arg("set")
+                .arg("project 2")
+                .arg("Fun")
+                .arg("3")
+                .assert()
+                .success();
+
+            build_command()?
+                .arg("display")
+                .arg("--ascii")
+                .assert()
+                .success()
+                .stdout(is_match(r"\|\s+\| Priority \(Workday\)\s+\|\s+1.5 \|")?)
+                .stdout(is_match(
+                    r"\| 2 \| project 2\s+\|\s+3 \|\s+4.5 \|\n\| 1 \| project 1",
+                )?);
+
+            Ok(())
+        },
+    )?;
+
+    Ok(())
+}
+
+#[test]
+fn test_display_truncated() -> Result<()> {
+    let temp_dir = create_test_config_dir()?;
+    let temp_dir_path = temp_dir.path();
+    let workspace_file = NamedTempFile::new()?;
+    fs::remove_file(workspace_file.path())?;
+    with_vars(
+        [(
+            TEST_CONFIG_DIR,
+            Some(temp_dir_path.as_os_str().to_str().unwrap()),
+        )],
+        || {
+            build_command()?
+                .arg("workspace")
+                .arg("new")
+                .arg(workspace_file.path())
+                .assert()
+                .success();
+
+            build_command()?
+                .arg("project")
+                .arg("add")
+                .arg("a project with a very long name")
+                .assert()
+                .success();
+
+            build_command()?
+                .arg("priority-set")
+                .arg("add")
+                .arg("Workday")
+                .assert()
+                .success();
+
+            build_command()?
+                .arg("display")
+                .arg("--width")
+                .arg("30")
+                .assert()
+                .success()
+                .stdout(contains("a project with a very long name").not())
+                .stdout(contains("…"));
+
+            Ok(())
+        },
+    )?;
+
+    Ok(())
+}
+
+#[test]
+fn test_display_with_method() -> Result<()> {
+    let temp_dir = create_test_config_dir()?;
+    let temp_dir_path = temp_dir.path();
+    let workspace_file = NamedTempFile::new()?;
+    fs::remove_file(workspace_file.path())?;
+    with_vars(
+        [(
+            TEST_CONFIG_DIR,
+            Some(temp_dir_path.as_os_str().to_str().unwrap()),
+        )],
+        || {
+            build_command()?
+                .arg("workspace")
+                .arg("new")
+                .arg(workspace_file.path())
+                .assert()
+                .success();
+
+            for project in ["project 1", "project 2"] {
+                build_command()?
+                    .arg("project")
+                    .arg("add")
+                    .arg(project)
+                    .assert()
+                    .success();
+            }
+
+            build_command()?
+                .arg("criterion")
+                .arg("add")
+                .arg("Fun")
+                .assert()
+                .success();
+
+            build_command()?
+                .arg("priority-set")
+                .arg("add")
+                .arg("Workday")
+                .assert()
+                .success();
+
+            build_command()?
+                .arg("weight")
+                .arg("set")
+                .arg("project 2")
+                .arg("Fun")
+                .arg("4")
+                .assert()
+                .success();
+
+            build_command()?
+                .arg("display")
+                .arg("--ascii")
+                .arg("--method")
+                .arg("min-max")
+                .assert()
+                .success()
+                .stdout(is_match(r"\| 2 \| project 2\s+\|\s+4 \|\s+1 \|")?);
+
+            build_command()?
+                .arg("display")
+                .arg("--method")
+                .arg("median")
+                .assert()
+                .failure();
+
+            Ok(())
+        },
+    )?;
+
+    Ok(())
+}
+
+#[test]
+fn test_display_topsis() -> Result<()> {
+    let temp_dir = create_test_config_dir()?;
+    let temp_dir_path = temp_dir.path();
+    let workspace_file = NamedTempFile::new()?;
+    fs::remove_file(workspace_file.path())?;
+    with_vars(
+        [(
+            TEST_CONFIG_DIR,
+            Some(temp_dir_path.as_os_str().to_str().unwrap()),
+        )],
+        || {
+            build_command()?
+                .arg("workspace")
+                .arg("new")
+                .arg(workspace_file.path())
+                .assert()
+                .success();
+
+            for project in ["project 1", "project 2"] {
+                build_command()?
+                    .args(["project", "add", project])
+                    .assert()
+                    .success();
+            }
+
+            for criterion in ["Fun", "Useful"] {
+                build_command()?
+                    .args(["criterion", "add", criterion])
+                    .assert()
+                    .success();
+            }
+
+            build_command()?
+                .args(["weight", "set", "project 1", "Fun", "3"])
+                .assert()
+                .success();
+
+            build_command()?
+                .args(["weight", "set", "project 2", "Useful", "4"])
+                .assert()
+                .success();
+
+            build_command()?
+                .args(["display", "--method", "topsis"])
+                .assert()
+                .failure()
+                .stderr(contains("no active priority set"));
+
+            build_command()?
+                .args(["priority-set", "add", "Workday"])
+                .assert()
+                .success();
+
+            build_command()?
+                .args(["criterion", "update-priority", "Fun", "3"])
+                .assert()
+                .success();
+
+            build_command()?
+                .args(["display", "--ascii", "--method", "topsis"])
+                .assert()
+                .success()
+                .stdout(contains("|   D+ |   D- | Score |"))
+                .stdout(is_match(
+                    r"\| 1 \| project 1\s+\|\s+3 \|\s+- \|\s+0.25 \|\s+0.75 \|\s+0.75 \|\n\| 2 \| project 2\s+\|\s+- \|\s+4 \|\s+0.75 \|\s+0.25 \|\s+0.25 \|",
+                )?);
+
+            Ok(())
+        },
+    )?;
+
+    Ok(())
+}
+
+#[test]
+fn test_display_all_sets() -> Result<()> {
+    let temp_dir = create_test_config_dir()?;
+    let temp_dir_path = temp_dir.path();
+    let workspace_file = NamedTempFile::new()?;
+    fs::remove_file(workspace_file.path())?;
+    with_vars(
+        [(
+            TEST_CONFIG_DIR,
+            Some(temp_dir_path.as_os_str().to_str().unwrap()),
+        )],
+        || {
+            build_command()?
+                .arg("workspace")
+                .arg("new")
+                .arg(workspace_file.path())
+                .assert()
+                .success();
+
+            for project in ["project 1", "project 2"] {
+                build_command()?
+                    .arg("project")
+                    .arg("add")
+                    .arg(project)
+                    .assert()
+                    .success();
+            }
+
+            for criterion in ["Fun", "Useful"] {
+                build_command()?
+                    .arg("criterion")
+                    .arg("add")
+                    .arg(criterion)
+                    .assert()
+                    .success();
+            }
+
+            build_command()?
+                .args(["weight", "set", "project 1", "Fun", "2"])
+                .assert()
+                .success();
+
+            build_command()?
+                .args(["weight", "set", "project 2", "Useful", "3"])
+                .assert()
+                .success();
+
+            build_command()?
+                .args(["display", "--all-sets"])
+                .assert()
+                .failure()
+                .stderr(contains("no priority sets"));
+
+            for priority_set in ["Workday", "Weekend"] {
+                build_command()?
+                    .args(["priority-set", "add", priority_set])
+                    .assert()
+                    .success();
+            }
+
+            build_command()?
+                .args(["priority-set", "activate", "Weekend"])
+                .assert()
+                .success();
+
+            build_command()?
+                .args(["criterion", "update-priority", "Fun", "2"])
+                .assert()
+                .success();
+
+            build_command()?
+                .args(["display", "--all-sets", "--ascii"])
+                .assert()
+                .success()
+                .stdout(is_match(r"\| # \| Project\s+\|\s+Workday \|\s+Weekend \|")?)
+                .stdout(is_match(
+                    r"\| 1 \| project 1 \|\s+2 \(#2\) \|\s+4 \(#1, \+1\) \|\n\| 2 \| project 2 \|\s+3 \(#1\) \|\s+3 \(#2, -1\) \|",
+                )?)
+                .stdout(contains("Rank correlation (Kendall tau):\n  Workday / Weekend: -1\n"));
+
+            Ok(())
+        },
+    )?;
+
+    Ok(())
+}
+
+#[test]
+fn test_display_with_dependencies() -> Result<()> {
+    let temp_dir = create_test_config_dir()?;
+    let temp_dir_path = temp_dir.path();
+    let workspace_file = NamedTempFile::new()?;
+    fs::remove_file(workspace_file.path())?;
+    with_vars(
+        [(
+            TEST_CONFIG_DIR,
+            Some(temp_dir_path.as_os_str().to_str().unwrap()),
+        )],
+        || {
+            build_command()?
+                .arg("workspace")
+                .arg("new")
+                .arg(workspace_file.path())
+                .assert()
+                .success();
+
+            build_command()?
+                .args(["priority-set", "add", "Workday"])
+                .assert()
+                .success();
+
+            build_command()?
+                .args(["criterion", "add", "Fun"])
+                .assert()
+                .success();
+
+            for (project, fun) in [("Foundation", "1"), ("House", "8"), ("Garden", "4")] {
+                build_command()?
+                    .args(["project", "add", project])
+                    .assert()
+                    .success();
+                build_command()?
+                    .args(["weight", "set", project, "Fun", fun])
+                    .assert()
+                    .success();
+            }
+
+            build_command()?
+                .args(["project", "depend", "House", "Foundation"])
+                .assert()
+                .success();
+
+            build_command()?
+                .args(["display", "--ascii", "--dependencies", "prerequisites"])
+                .assert()
+                .success()
+                .stdout(is_match(
+                    r"\| 3 \| Garden\s+\|.*\n\| 1 \| Foundation\s+\|.*\n\| 2 \| House\s+\|",
+                )?);
+
+            build_command()?
+                .args(["display", "--ascii", "-d", "inherit", "--share", "0.5"])
+                .assert()
+                .success()
+                .stdout(contains("| Score | Adjusted |"))
+                .stdout(is_match(
+                    r"\| 2 \| House\s+\|\s+8 \|\s+8 \|\s+8 \|\n\| 1 \| Foundation\s+\|\s+1 \|\s+1 \|\s+5 \|",
+                )?);
+
+            Ok(())
+        },
+    )?;
+
+    Ok(())
+}
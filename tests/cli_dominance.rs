@@ -0,0 +1,78 @@
+use std::fs;
+
+use anyhow::{Ok, Result};
+use common::{build_command, create_test_config_dir, TEST_CONFIG_DIR};
+use predicates::str::contains;
+use temp_env::with_vars;
+use tempfile::NamedTempFile;
+
+mod common;
+
+#[test]
+fn test_dominance() -> Result<()> {
+    let temp_dir = create_test_config_dir()?;
+    let temp_dir_path = temp_dir.path();
+    let workspace_file = NamedTempFile::new()?;
+    fs::remove_file(workspace_file.path())?;
+    with_vars(
+        [(
+            TEST_CONFIG_DIR,
+            Some(temp_dir_path.as_os_str().to_str().unwrap()),
+        )],
+        || {
+            build_command()?
+                .arg("workspace")
+                .arg("new")
+                .arg(workspace_file.path())
+                .assert()
+                .success();
+
+            for project in ["Cheap", "Expensive", "Fancy"] {
+                build_command()?
+                    .arg("project")
+                    .arg("add")
+                    .arg(project)
+                    .assert()
+                    .success();
+            }
+
+            build_command()?
+                .args(["criterion", "add", "Cost", "--cost"])
+                .assert()
+                .success();
+
+            build_command()?
+                .args(["criterion", "add", "Fun"])
+                .assert()
+                .success();
+
+            for (project, cost, fun) in [
+                ("Cheap", "1", "2"),
+                ("Expensive", "3", "2"),
+                ("Fancy", "3", "5"),
+            ] {
+                build_command()?
+                    .args(["weight", "set", project, "Cost", cost])
+                    .assert()
+                    .success();
+                build_command()?
+                    .args(["weight", "set", project, "Fun", fun])
+                    .assert()
+                    .success();
+            }
+
+            build_command()?
+                .arg("dominance")
+                .assert()
+                .success()
+                .stdout(contains("Pareto-optimal projects:\n  Cheap\n  Fancy\n"))
+                .stdout(contains(
+                    "Dominated projects:\n  Expensive (dominated by 'Cheap', 'Fancy')",
+                ));
+
+            Ok(())
+        },
+    )?;
+
+    Ok(())
+}
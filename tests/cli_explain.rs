@@ -0,0 +1,118 @@
+use std::fs;
+
+use anyhow::{Ok, Result};
+use common::{build_command, create_test_config_dir, TEST_CONFIG_DIR};
+use predicates::str::{contains, is_match};
+use temp_env::with_vars;
+use tempfile::NamedTempFile;
+
+mod common;
+
+#[test]
+fn test_explain() -> Result<()> {
+    let temp_dir = create_test_config_dir()?;
+    let temp_dir_path = temp_dir.path();
+    let workspace_file = NamedTempFile::new()?;
+    fs::remove_file(workspace_file.path())?;
+    with_vars(
+        [(
+            TEST_CONFIG_DIR,
+            Some(temp_dir_path.as_os_str().to_str().unwrap()),
+        )],
+        || {
+            build_command()?
+                .arg("workspace")
+                .arg("new")
+                .arg(workspace_file.path())
+                .assert()
+                .success();
+
+            for project in ["Alpha", "Beta"] {
+                build_command()?
+                    .arg("project")
+                    .arg("add")
+                    .arg(project)
+                    .assert()
+                    .success();
+            }
+
+            for criterion in ["Fun", "Useful"] {
+                build_command()?
+                    .arg("criterion")
+                    .arg("add")
+                    .arg(criterion)
+                    .assert()
+                    .success();
+            }
+
+            build_command()?
+                .arg("priority-set")
+                .arg("add")
+                .arg("Workday")
+                .assert()
+                .success();
+
+            build_command()?
+                .args(["criterion", "update-priority", "Useful", "3"])
+                .assert()
+                .success();
+
+            for (project, criterion, weight) in [
+                ("Alpha", "Fun", "2"),
+                ("Alpha", "Useful", "1"),
+                ("Beta", "Fun", "4"),
+                ("Beta", "Useful", "2"),
+            ] {
+                build_command()?
+                    .args(["weight", "set", project, criterion, weight])
+                    .assert()
+                    .success();
+            }
+
+            build_command()?
+                .args(["explain", "alpha"])
+                .assert()
+                .success()
+                .stdout(contains(
+                    "Score of 'Alpha' under priority set 'Workday' (weighted-sum): 5",
+                ))
+                .stdout(is_match(r"Fun\s+2\s+1\s+2\s+40.0%")?)
+                .stdout(is_match(r"Useful\s+1\s+3\s+3\s+60.0%")?);
+
+            build_command()?
+                .args(["explain", "Alpha", "2"])
+                .assert()
+                .success()
+                .stdout(contains(
+                    "Under priority set 'Workday' (weighted-sum), 'Alpha' scores 5 and 'Beta' scores 10",
+                ))
+                .stdout(is_match(
+                    r"Criterion\s+Alpha\s+Beta\s+Difference\nUseful\s+3\s+6\s+-3\nFun\s+2\s+4\s+-2",
+                )?);
+
+            build_command()?
+                .args(["explain", "Beta", "--method", "min-max"])
+                .assert()
+                .success()
+                .stdout(contains(
+                    "Score of 'Beta' under priority set 'Workday' (min-max): 4",
+                ))
+                .stdout(is_match(r"Useful\s+2\s+3\s+3\s+75.0%")?);
+
+            build_command()?
+                .args(["explain", "Beta", "--method", "topsis"])
+                .assert()
+                .failure();
+
+            build_command()?
+                .args(["explain", "Gamma"])
+                .assert()
+                .failure()
+                .stderr(contains("no project Gamma"));
+
+            Ok(())
+        },
+    )?;
+
+    Ok(())
+}
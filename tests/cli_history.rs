@@ -0,0 +1,100 @@
+use std::fs;
+
+use anyhow::{Ok, Result};
+use common::{build_command, create_test_config_dir, TEST_CONFIG_DIR};
+use predicates::str::{contains, diff, is_match};
+use temp_env::with_vars;
+use tempfile::NamedTempFile;
+
+mod common;
+
+#[test]
+fn test_undo_and_redo() -> Result<()> {
+    let temp_dir = create_test_config_dir()?;
+    let temp_dir_path = temp_dir.path();
+    let workspace_file = NamedTempFile::new()?;
+    fs::remove_file(workspace_file.path())?;
+    with_vars(
+        [(
+            TEST_CONFIG_DIR,
+            Some(temp_dir_path.as_os_str().to_str().unwrap()),
+        )],
+        || {
+            build_command()?
+                .arg("workspace")
+                .arg("new")
+                .arg(workspace_file.path())
+                .assert()
+                .success();
+
+            build_command()?
+                .arg("history")
+                .assert()
+                .success()
+                .stdout(diff("No changes recorded\n"));
+
+            build_command()?
+                .args(["project", "add", "project 1"])
+                .assert()
+                .success();
+
+            build_command()?
+                .args(["criterion", "add", "Fun"])
+                .assert()
+                .success();
+
+            for weight in ["5", "7"] {
+                build_command()?
+                    .args(["weight", "set", "1", "Fun", weight])
+                    .assert()
+                    .success();
+            }
+
+            build_command()?
+                .arg("undo")
+                .assert()
+                .success()
+                .stdout(diff("Undid 'weight set 1 Fun 7'\n"));
+
+            build_command()?
+                .args(["weight", "get", "1", "Fun"])
+                .assert()
+                .success()
+                .stdout(diff("5\n"));
+
+            build_command()?
+                .arg("history")
+                .assert()
+                .success()
+                .stdout(is_match(concat!(
+                    r"^1 \S+Z project add 'project 1'\n",
+                    r"2 \S+Z criterion add Fun\n",
+                    r"3 \S+Z weight set 1 Fun 5\n",
+                    r"4 \S+Z weight set 1 Fun 7 \(undone\)\n",
+                    r"5 \S+Z undo weight set 1 Fun 7\n$",
+                ))?);
+
+            build_command()?
+                .arg("redo")
+                .assert()
+                .success()
+                .stdout(diff("Redid 'weight set 1 Fun 7'\n"));
+
+            build_command()?
+                .arg("redo")
+                .assert()
+                .failure()
+                .stderr(contains("nothing to redo"));
+
+            build_command()?
+                .args(["weight", "get", "1", "Fun"])
+                .assert()
+                .success()
+                .stdout(diff("7\n"));
+
+            Ok(())
+        },
+    )?;
+
+    Ok(())
+}
@@ -0,0 +1,117 @@
+use std::fs;
+
+use anyhow::{Ok, Result};
+use common::{build_command, create_test_config_dir, TEST_CONFIG_DIR};
+use predicates::str::contains;
+use temp_env::with_vars;
+use tempfile::NamedTempFile;
+
+mod common;
+
+#[test]
+fn test_portfolio() -> Result<()> {
+    let temp_dir = create_test_config_dir()?;
+    let temp_dir_path = temp_dir.path();
+    let workspace_file = NamedTempFile::new()?;
+    fs::remove_file(workspace_file.path())?;
+    with_vars(
+        [(
+            TEST_CONFIG_DIR,
+            Some(temp_dir_path.as_os_str().to_str().unwrap()),
+        )],
+        || {
+            build_command()?
+                .arg("workspace")
+                .arg("new")
+                .arg(workspace_file.path())
+                .assert()
+                .success();
+
+            build_command()?
+                .args(["priority-set", "add", "Workday"])
+                .assert()
+                .success();
+
+            build_command()?
+                .args(["criterion", "add", "Fun"])
+                .assert()
+                .success();
+
+            build_command()?
+                .args(["criterion", "add", "Weeks", "0", "--cost"])
+                .assert()
+                .success();
+
+            for (project, fun, weeks) in [
+                ("Alpha", "6", "5"),
+                ("Beta", "5", "4"),
+                ("Gamma", "4", "3"),
+                ("Delta", "3", "2"),
+            ] {
+                build_command()?
+                    .args(["project", "add", project])
+                    .assert()
+                    .success();
+                build_command()?
+                    .args(["weight", "set", project, "Fun", fun])
+                    .assert()
+                    .success();
+                build_command()?
+                    .args(["weight", "set", project, "Weeks", weeks])
+                    .assert()
+                    .success();
+            }
+
+            build_command()?
+                .args(["portfolio", "--budget", "9"])
+                .assert()
+                .failure()
+                .stderr(contains("no budget cost criterion"));
+
+            build_command()?
+                .args([
+                    "portfolio",
+                    "--budget",
+                    "9",
+                    "--criterion",
+                    "weeks",
+                    "-a",
+                    "1",
+                ])
+                .assert()
+                .success()
+                .stdout(contains(concat!(
+                    "Best portfolio within a budget of 9 (Weeks):\n",
+                    "  Beta\n",
+                    "  Gamma\n",
+                    "  Delta\n",
+                    "Total score 12, cost 9, leftover budget 0\n",
+                    "Runner-ups:\n",
+                    "  2. score 11, cost 9, leftover 0: Alpha, Beta\n",
+                )));
+
+            build_command()?
+                .args(["criterion", "update", "Weeks", "--budget-cost"])
+                .assert()
+                .success();
+
+            build_command()?
+                .args(["criterion", "list"])
+                .assert()
+                .success()
+                .stdout(contains("Weeks 0 (cost, budget cost)"));
+
+            build_command()?
+                .args(["portfolio", "-b", "6", "-a", "0"])
+                .assert()
+                .success()
+                .stdout(contains(
+                    "  Beta\n  Delta\nTotal score 8, cost 6, leftover budget 0\n",
+                ));
+
+            Ok(())
+        },
+    )?;
+
+    Ok(())
+}
@@ -0,0 +1,452 @@
+use std::fs;
+
+use anyhow::{Ok, Result};
+use common::{build_command, create_test_config_dir, TEST_CONFIG_DIR};
+use mockall::PredicateBooleanExt;
+use predicates::str::{contains, is_match};
+use temp_env::with_vars;
+use tempfile::NamedTempFile;
+
+mod common;
+
+#[test]
+fn test_add_priority_set() -> Result<()> {
+    let temp_dir = create_test_config_dir()?;
+    let temp_dir_path = temp_dir.path();
+    let workspace_file = NamedTempFile::new()?;
+    fs::remove_file(workspace_file.path())?;
+    with_vars(
+        [(
+            TEST_CONFIG_DIR,
+            Some(temp_dir_path.as_os_str().to_str().unwrap()),
+        )],
+        || {
+            build_command()?
+                .arg("workspace")
+                .arg("new")
+                .arg(workspace_file.path())
+                .assert()
+                .success();
+
+            build_command()?
+                .arg("priority-set")
+                .arg("add")
+                .arg("Workday")
+                .assert()
+                .success();
+
+            build_command()?
+                .arg("priority-set")
+                .arg("add")
+                .arg("Weekend")
+                .assert()
+                .success();
+
+            build_command()?
+                .arg("priority-set")
+                .arg("add")
+                .arg("Weekend")
+                .assert()
+                .failure();
+
+            build_command()?
+                .arg("priority-set")
+                .arg("list")
+                .assert()
+                .success()
+                .stdout(is_match(r"\* 1 Workday\n  2 Weekend\n")?);
+
+            Ok(())
+        },
+    )?;
+
+    Ok(())
+}
+
+#[test]
+fn test_activate_priority_set() -> Result<()> {
+    let temp_dir = create_test_config_dir()?;
+    let temp_dir_path = temp_dir.path();
+    let workspace_file = NamedTempFile::new()?;
+    fs::remove_file(workspace_file.path())?;
+    with_vars(
+        [(
+            TEST_CONFIG_DIR,
+            Some(temp_dir_path.as_os_str().to_str().unwrap()),
+        )],
+        || {
+            build_command()?
+                .arg("workspace")
+                .arg("new")
+                .arg(workspace_file.path())
+                .assert()
+                .success();
+
+            for name in ["Workday", "Weekend"] {
+                build_command()?
+                    .arg("priority-set")
+                    .arg("add")
+                    .arg(name)
+                    .assert()
+                    .success();
+            }
+
+            build_command()?
+                .arg("criterion")
+                .arg("add")
+                .arg("Fun")
+                .arg("2")
+                .assert()
+                .success();
+
+            build_command()?
+                .arg("priority-set")
+                .arg("activate")
+                .arg("Weekend")
+                .assert()
+                .success();
+
+            build_command()?
+                .arg("priority-set")
+                .arg("list")
+                .assert()
+                .stdout(is_match(r"  1 Workday\n\* 2 Weekend\n")?);
+
+            build_command()?
+                .arg("criterion")
+                .arg("list")
+                .assert()
+                .stdout(is_match("1 Fun 1\n")?);
+
+            build_command()?
+                .arg("priority-set")
+                .arg("activate")
+                .arg("1")
+                .assert()
+                .success();
+
+            build_command()?
+                .arg("criterion")
+                .arg("list")
+                .assert()
+                .stdout(is_match("1 Fun 2\n")?);
+
+            build_command()?
+                .arg("priority-set")
+                .arg("activate")
+                .arg("Holiday")
+                .assert()
+                .failure();
+
+            Ok(())
+        },
+    )?;
+
+    Ok(())
+}
+
+#[test]
+fn test_remove_and_reorder_priority_sets() -> Result<()> {
+    let temp_dir = create_test_config_dir()?;
+    let temp_dir_path = temp_dir.path();
+    let workspace_file = NamedTempFile::new()?;
+    fs::remove_file(workspace_file.path())?;
+    with_vars(
+        [(
+            TEST_CONFIG_DIR,
+            Some(temp_dir_path.as_os_str().to_str().unwrap()),
+        )],
+        || {
+            build_command()?
+                .arg("workspace")
+                .arg("new")
+                .arg(workspace_file.path())
+                .assert()
+                .success();
+
+            for name in ["Workday", "Weekend", "Holiday"] {
+                build_command()?
+                    .arg("priority-set")
+                    .arg("add")
+                    .arg(name)
+                    .assert()
+                    .success();
+            }
+
+            build_command()?
+                .arg("priority-set")
+                .arg("reorder")
+                .arg("Holiday")
+                .assert()
+                .success();
+
+            build_command()?
+                .arg("priority-set")
+                .arg("list")
+                .assert()
+                .stdout(is_match(r"  1 Holiday\n\* 2 Workday\n  3 Weekend\n")?);
+
+            build_command()?
+                .arg("priority-set")
+                .arg("remove")
+                .arg("2")
+                .assert()
+                .success();
+
+            build_command()?
+                .arg("priority-set")
+                .arg("list")
+                .assert()
+                .stdout(contains("Workday").not())
+                .stdout(contains("*").not());
+
+            Ok(())
+        },
+    )?;
+
+    Ok(())
+}
+
+#[test]
+fn test_compare_criteria() -> Result<()> {
+    let temp_dir = create_test_config_dir()?;
+    let temp_dir_path = temp_dir.path();
+    let workspace_file = NamedTempFile::new()?;
+    fs::remove_file(workspace_file.path())?;
+    with_vars(
+        [(
+            TEST_CONFIG_DIR,
+            Some(temp_dir_path.as_os_str().to_str().unwrap()),
+        )],
+        || {
+            build_command()?
+                .arg("workspace")
+                .arg("new")
+                .arg(workspace_file.path())
+                .assert()
+                .success();
+
+            build_command()?
+                .arg("priority-set")
+                .arg("add")
+                .arg("Workday")
+                .assert()
+                .success();
+
+            for name in ["Fun", "Useful"] {
+                build_command()?
+                    .arg("criterion")
+                    .arg("add")
+                    .arg(name)
+                    .assert()
+                    .success();
+            }
+
+            build_command()?
+                .arg("priority-set")
+                .arg("compare")
+                .write_stdin("12\n1/3\n")
+                .assert()
+                .success()
+                .stdout(contains("Invalid comparison"));
+
+            build_command()?
+                .arg("criterion")
+                .arg("list")
+                .assert()
+                .stdout(is_match("1 Fun +0.25\n2 Useful +0.75\n")?);
+
+            // the previous comparison is offered as default
+            build_command()?
+                .arg("priority-set")
+                .arg("compare")
+                .arg("Workday")
+                .write_stdin("\n")
+                .assert()
+                .success()
+                .stdout(contains("[1/3]"));
+
+            Ok(())
+        },
+    )?;
+
+    Ok(())
+}
+
+#[test]
+fn test_compare_inconsistent_criteria() -> Result<()> {
+    let temp_dir = create_test_config_dir()?;
+    let temp_dir_path = temp_dir.path();
+    let workspace_file = NamedTempFile::new()?;
+    fs::remove_file(workspace_file.path())?;
+    with_vars(
+        [(
+            TEST_CONFIG_DIR,
+            Some(temp_dir_path.as_os_str().to_str().unwrap()),
+        )],
+        || {
+            build_command()?
+                .arg("workspace")
+                .arg("new")
+                .arg(workspace_file.path())
+                .assert()
+                .success();
+
+            build_command()?
+                .arg("priority-set")
+                .arg("add")
+                .arg("Workday")
+                .assert()
+                .success();
+
+            for name in ["Fun", "Useful", "Cheap"] {
+                build_command()?
+                    .arg("criterion")
+                    .arg("add")
+                    .arg(name)
+                    .assert()
+                    .success();
+            }
+
+            build_command()?
+                .arg("priority-set")
+                .arg("compare")
+                .write_stdin("9\n1/9\n9\n")
+                .assert()
+                .failure()
+                .stderr(contains("inconsistent"));
+
+            build_command()?
+                .arg("criterion")
+                .arg("list")
+                .assert()
+                .stdout(is_match("1 Fun +1\n")?);
+
+            build_command()?
+                .arg("priority-set")
+                .arg("compare")
+                .arg("--force")
+                .write_stdin("9\n1/9\n9\n")
+                .assert()
+                .success()
+                .stderr(contains("Warning"));
+
+            build_command()?
+                .arg("criterion")
+                .arg("list")
+                .assert()
+                .stdout(is_match("1 Fun +0.3")?);
+
+            Ok(())
+        },
+    )?;
+
+    Ok(())
+}
+
+#[test]
+fn test_constrain_criteria() -> Result<()> {
+    let temp_dir = create_test_config_dir()?;
+    let temp_dir_path = temp_dir.path();
+    let workspace_file = NamedTempFile::new()?;
+    fs::remove_file(workspace_file.path())?;
+    with_vars(
+        [(
+            TEST_CONFIG_DIR,
+            Some(temp_dir_path.as_os_str().to_str().unwrap()),
+        )],
+        || {
+            build_command()?
+                .arg("workspace")
+                .arg("new")
+                .arg(workspace_file.path())
+                .assert()
+                .success();
+
+            for project in ["Safe", "Risky"] {
+                build_command()?
+                    .args(["project", "add", project])
+                    .assert()
+                    .success();
+            }
+
+            build_command()?
+                .args(["criterion", "add", "Risk"])
+                .assert()
+                .success();
+
+            for priority_set in ["Workday", "Weekend"] {
+                build_command()?
+                    .args(["priority-set", "add", priority_set])
+                    .assert()
+                    .success();
+            }
+
+            build_command()?
+                .args(["weight", "set", "Safe", "Risk", "1"])
+                .assert()
+                .success();
+
+            build_command()?
+                .args(["weight", "set", "Risky", "Risk", "5"])
+                .assert()
+                .success();
+
+            build_command()?
+                .args(["priority-set", "constrain", "Risk"])
+                .assert()
+                .failure();
+
+            build_command()?
+                .args(["priority-set", "constrain", "risk", "--max", "3"])
+                .assert()
+                .success()
+                .stdout(contains(
+                    "Constraining criterion 'Risk' to ≤ 3 in priority set 'Workday'",
+                ));
+
+            build_command()?
+                .args(["priority-set", "constrain", "Risk", "--max", "8", "-s", "2"])
+                .assert()
+                .success();
+
+            build_command()?
+                .args(["priority-set", "list"])
+                .assert()
+                .success()
+                .stdout(contains(
+                    "* 1 Workday\n    Risk ≤ 3\n  2 Weekend\n    Risk ≤ 8\n",
+                ));
+
+            build_command()?
+                .args(["display", "--ascii"])
+                .assert()
+                .success()
+                .stdout(contains("| Safe ").and(contains("| Risky ").not()))
+                .stdout(contains(
+                    "Vetoed projects:\n  Risky (Risk: 5 is above the maximum of 3)\n",
+                ));
+
+            build_command()?
+                .args(["priority-set", "unconstrain", "Risk"])
+                .assert()
+                .success();
+
+            build_command()?
+                .args(["priority-set", "unconstrain", "Risk"])
+                .assert()
+                .failure()
+                .stderr(contains("no constraint on criterion Risk"));
+
+            build_command()?
+                .args(["display"])
+                .assert()
+                .success()
+                .stdout(contains("Risky").and(contains("Vetoed").not()));
+
+            Ok(())
+        },
+    )?;
+
+    Ok(())
+}
@@ -3,7 +3,7 @@ use std::fs;
 use anyhow::{Ok, Result};
 use common::{build_command, create_test_config_dir, TEST_CONFIG_DIR};
 use mockall::PredicateBooleanExt;
-use predicates::str::contains;
+use predicates::str::{contains, diff, is_match};
 use temp_env::with_vars;
 use tempfile::NamedTempFile;
 
@@ -134,3 +134,369 @@ fn test_add_project_invalid_name() -> Result<()> {
 
     Ok(())
 }
+
+#[test]
+fn test_remove_projects() -> Result<()> {
+    let temp_dir = create_test_config_dir()?;
+    let temp_dir_path = temp_dir.path();
+    let workspace_file = NamedTempFile::new()?;
+    fs::remove_file(workspace_file.path())?;
+    with_vars(
+        [(
+            TEST_CONFIG_DIR,
+            Some(temp_dir_path.as_os_str().to_str().unwrap()),
+        )],
+        || {
+            build_command()?
+                .arg("workspace")
+                .arg("new")
+                .arg(workspace_file.path())
+                .assert()
+                .success();
+
+            for project in ["project 1", "project 2", "project 3"] {
+                build_command()?
+                    .arg("project")
+                    .arg("add")
+                    .arg(project)
+                    .assert()
+                    .success();
+            }
+
+            build_command()?
+                .arg("project")
+                .arg("remove")
+                .arg("--dry-run")
+                .arg("project 1")
+                .assert()
+                .success()
+                .stdout(is_match("1 project 2\n2 project 3\n")?);
+
+            build_command()?
+                .arg("project")
+                .arg("remove")
+                .arg("project 1")
+                .arg("3")
+                .assert()
+                .success();
+
+            build_command()?
+                .arg("project")
+                .arg("list")
+                .assert()
+                .stdout(diff("1 project 2\n"));
+
+            build_command()?
+                .arg("project")
+                .arg("remove")
+                .arg("project 1")
+                .assert()
+                .failure();
+
+            Ok(())
+        },
+    )?;
+
+    Ok(())
+}
+
+#[test]
+fn test_reorder_projects() -> Result<()> {
+    let temp_dir = create_test_config_dir()?;
+    let temp_dir_path = temp_dir.path();
+    let workspace_file = NamedTempFile::new()?;
+    fs::remove_file(workspace_file.path())?;
+    with_vars(
+        [(
+            TEST_CONFIG_DIR,
+            Some(temp_dir_path.as_os_str().to_str().unwrap()),
+        )],
+        || {
+            build_command()?
+                .arg("workspace")
+                .arg("new")
+                .arg(workspace_file.path())
+                .assert()
+                .success();
+
+            for project in ["project 1", "project 2", "project 3"] {
+                build_command()?
+                    .arg("project")
+                    .arg("add")
+                    .arg(project)
+                    .assert()
+                    .success();
+            }
+
+            build_command()?
+                .arg("project")
+                .arg("reorder")
+                .arg("-n")
+                .arg("3")
+                .assert()
+                .success()
+                .stdout(is_match("1 project 3\n2 project 1\n3 project 2\n")?);
+
+            build_command()?
+                .arg("project")
+                .arg("list")
+                .assert()
+                .stdout(diff("1 project 1\n2 project 2\n3 project 3\n"));
+
+            build_command()?
+                .arg("project")
+                .arg("reorder")
+                .arg("project 3")
+                .arg("project 2")
+                .assert()
+                .success();
+
+            build_command()?
+                .arg("project")
+                .arg("list")
+                .assert()
+                .stdout(diff("1 project 3\n2 project 2\n3 project 1\n"));
+
+            Ok(())
+        },
+    )?;
+
+    Ok(())
+}
+
+#[test]
+fn test_project_dependencies() -> Result<()> {
+    let temp_dir = create_test_config_dir()?;
+    let temp_dir_path = temp_dir.path();
+    let workspace_file = NamedTempFile::new()?;
+    fs::remove_file(workspace_file.path())?;
+    with_vars(
+        [(
+            TEST_CONFIG_DIR,
+            Some(temp_dir_path.as_os_str().to_str().unwrap()),
+        )],
+        || {
+            build_command()?
+                .arg("workspace")
+                .arg("new")
+                .arg(workspace_file.path())
+                .assert()
+                .success();
+
+            for project in ["Foundation", "Walls", "House", "Garden"] {
+                build_command()?
+                    .args(["project", "add", project])
+                    .assert()
+                    .success();
+            }
+
+            build_command()?
+                .args(["project", "depend", "House", "Foundation", "walls"])
+                .assert()
+                .success()
+                .stdout(contains("Project 'House' now depends on 'Walls'"));
+
+            build_command()?
+                .args(["project", "depend", "Walls", "1"])
+                .assert()
+                .success();
+
+            build_command()?
+                .args(["project", "depend", "Foundation", "House"])
+                .assert()
+                .failure()
+                .stderr(contains(
+                    "dependency would create the cycle Foundation -> House -> Foundation",
+                ))
+                .stdout(contains("now depends").not());
+
+            build_command()?
+                .args(["project", "deps"])
+                .assert()
+                .success()
+                .stdout(diff(concat!(
+                    "House\n",
+                    "├── Foundation\n",
+                    "└── Walls\n",
+                    "    └── Foundation\n",
+                    "Garden\n",
+                )));
+
+            build_command()?
+                .args(["project", "deps", "walls", "--ascii"])
+                .assert()
+                .success()
+                .stdout(diff("Walls\n`-- Foundation\n"));
+
+            build_command()?
+                .args(["project", "undepend", "House", "Walls"])
+                .assert()
+                .success();
+
+            build_command()?
+                .args(["project", "undepend", "House", "Walls"])
+                .assert()
+                .failure()
+                .stderr(contains("project House doesn't depend on Walls"));
+
+            build_command()?
+                .args(["project", "remove", "Foundation"])
+                .assert()
+                .success();
+
+            build_command()?
+                .args(["project", "deps"])
+                .assert()
+                .success()
+                .stdout(diff("Walls\nHouse\nGarden\n"));
+
+            Ok(())
+        },
+    )?;
+
+    Ok(())
+}
+
+#[test]
+fn test_project_status() -> Result<()> {
+    let temp_dir = create_test_config_dir()?;
+    let temp_dir_path = temp_dir.path();
+    let workspace_file = NamedTempFile::new()?;
+    fs::remove_file(workspace_file.path())?;
+    with_vars(
+        [(
+            TEST_CONFIG_DIR,
+            Some(temp_dir_path.as_os_str().to_str().unwrap()),
+        )],
+        || {
+            build_command()?
+                .arg("workspace")
+                .arg("new")
+                .arg(workspace_file.path())
+                .assert()
+                .success();
+
+            build_command()?
+                .args(["priority-set", "add", "Workday"])
+                .assert()
+                .success();
+
+            build_command()?
+                .args(["criterion", "add", "Fun"])
+                .assert()
+                .success();
+
+            for (project, fun) in [("Foundation", "1"), ("House", "8"), ("Garden", "4")] {
+                build_command()?
+                    .args(["project", "add", project])
+                    .assert()
+                    .success();
+                build_command()?
+                    .args(["weight", "set", project, "Fun", fun])
+                    .assert()
+                    .success();
+            }
+
+            build_command()?
+                .args(["project", "status", "Foundation", "done"])
+                .assert()
+                .success()
+                .stdout(contains("Project 'Foundation' is now done"));
+
+            build_command()?
+                .args(["project", "status", "1", "done"])
+                .assert()
+                .failure()
+                .stderr(contains("project Foundation is already done"));
+
+            build_command()?
+                .args(["project", "status", "Foundation"])
+                .assert()
+                .success()
+                .stdout(is_match(
+                    r"^Status: done\nHistory:\n  \d{4}-\d\d-\d\dT[\d:]+Z idea\n  \d{4}-\d\d-\d\dT[\d:]+Z done\n$",
+                )?);
+
+            build_command()?
+                .args(["project", "archive", "Foundation", "3"])
+                .assert()
+                .success();
+
+            build_command()?
+                .args(["project", "list"])
+                .assert()
+                .success()
+                .stdout(diff("2 House\n"));
+
+            build_command()?
+                .args(["project", "list", "--archived"])
+                .assert()
+                .success()
+                .stdout(diff(
+                    "1 Foundation (done, archived)\n2 House\n3 Garden (archived)\n",
+                ));
+
+            build_command()?
+                .arg("display")
+                .assert()
+                .success()
+                .stdout(contains("House").and(contains("Garden").not()));
+
+            build_command()?
+                .args(["display", "--archived"])
+                .assert()
+                .success()
+                .stdout(is_match(r"3 │ Garden\s+│\s+4")?);
+
+            build_command()?
+                .args(["explain", "Garden"])
+                .assert()
+                .failure()
+                .stderr(contains("project Garden is archived"));
+
+            build_command()?
+                .args(["project", "restore", "Garden"])
+                .assert()
+                .success();
+
+            build_command()?
+                .args(["project", "restore", "Garden"])
+                .assert()
+                .failure()
+                .stderr(contains("project Garden isn't archived"));
+
+            build_command()?
+                .args(["project", "status", "House", "dropped"])
+                .assert()
+                .success();
+
+            build_command()?
+                .arg("display")
+                .assert()
+                .success()
+                .stdout(contains("Garden").and(contains("House").not()));
+
+            build_command()?
+                .args(["display", "--closed"])
+                .assert()
+                .success()
+                .stdout(is_match(r"2 │ House\s+│\s+8")?);
+
+            build_command()?
+                .args(["explain", "House"])
+                .assert()
+                .failure()
+                .stderr(contains("project House is dropped"));
+
+            build_command()?
+                .args(["project", "list"])
+                .assert()
+                .success()
+                .stdout(diff("2 House (dropped)\n3 Garden\n"));
+
+            Ok(())
+        },
+    )?;
+
+    Ok(())
+}
@@ -0,0 +1,110 @@
+use std::fs;
+
+use anyhow::{Ok, Result};
+use common::{build_command, create_test_config_dir, TEST_CONFIG_DIR};
+use predicates::str::{contains, diff};
+use temp_env::with_vars;
+use tempfile::NamedTempFile;
+
+mod common;
+
+#[test]
+fn test_snapshot_and_trend() -> Result<()> {
+    let temp_dir = create_test_config_dir()?;
+    let temp_dir_path = temp_dir.path();
+    let workspace_file = NamedTempFile::new()?;
+    fs::remove_file(workspace_file.path())?;
+    with_vars(
+        [(
+            TEST_CONFIG_DIR,
+            Some(temp_dir_path.as_os_str().to_str().unwrap()),
+        )],
+        || {
+            build_command()?
+                .arg("workspace")
+                .arg("new")
+                .arg(workspace_file.path())
+                .assert()
+                .success();
+
+            build_command()?
+                .args(["priority-set", "add", "Workday"])
+                .assert()
+                .success();
+
+            build_command()?
+                .args(["criterion", "add", "Fun"])
+                .assert()
+                .success();
+
+            for (project, fun) in [("Alpha", "3"), ("Beta", "2"), ("Gamma", "1")] {
+                build_command()?
+                    .args(["project", "add", project])
+                    .assert()
+                    .success();
+                build_command()?
+                    .args(["weight", "set", project, "Fun", fun])
+                    .assert()
+                    .success();
+            }
+
+            build_command()?
+                .arg("trend")
+                .assert()
+                .success()
+                .stdout(diff("No snapshots of priority set 'Workday'\n"));
+
+            build_command()?
+                .args(["snapshot", "Sprint 1"])
+                .assert()
+                .success()
+                .stdout(diff("Recorded snapshot 'Sprint 1'\n"));
+
+            build_command()?
+                .args(["weight", "set", "Gamma", "Fun", "4"])
+                .assert()
+                .success();
+
+            build_command()?
+                .args(["project", "remove", "Beta"])
+                .assert()
+                .success();
+
+            build_command()?
+                .args(["snapshot", "Sprint 2"])
+                .assert()
+                .success();
+
+            build_command()?
+                .args(["snapshot", "Sprint 2"])
+                .assert()
+                .failure()
+                .stderr(contains("snapshot Sprint 2 already exists"));
+
+            build_command()?
+                .arg("trend")
+                .assert()
+                .success()
+                .stdout(diff(concat!(
+                    "Scores (ranks) under priority set 'Workday':\n",
+                    "Project  Sprint 1  Sprint 2  Change\n",
+                    "Gamma      1 (#3)    4 (#1)      +2\n",
+                    "Alpha      3 (#1)    3 (#2)      -1\n",
+                    "Beta       2 (#2)         -\n",
+                    "Biggest movers:\n",
+                    "  Gamma up 2 (#3 -> #1)\n",
+                    "  Alpha down 1 (#1 -> #2)\n",
+                )));
+
+            build_command()?
+                .args(["trend", "--movers", "1"])
+                .assert()
+                .success()
+                .stdout(contains("Biggest movers:\n  Gamma up 2 (#3 -> #1)\n"));
+
+            Ok(())
+        },
+    )?;
+
+    Ok(())
+}
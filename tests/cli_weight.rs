@@ -0,0 +1,396 @@
+use std::{fs, io::Write};
+
+use anyhow::{Ok, Result};
+use common::{build_command, create_test_config_dir, TEST_CONFIG_DIR};
+use mockall::PredicateBooleanExt;
+use predicates::str::{contains, diff};
+use temp_env::with_vars;
+use tempfile::NamedTempFile;
+
+mod common;
+
+#[test]
+fn test_set_and_get_weight() -> Result<()> {
+    let temp_dir = create_test_config_dir()?;
+    let temp_dir_path = temp_dir.path();
+    let workspace_file = NamedTempFile::new()?;
+    fs::remove_file(workspace_file.path())?;
+    with_vars(
+        [(
+            TEST_CONFIG_DIR,
+            Some(temp_dir_path.as_os_str().to_str().unwrap()),
+        )],
+        || {
+            build_command()?
+                .arg("workspace")
+                .arg("new")
+                .arg(workspace_file.path())
+                .assert()
+                .success();
+
+            for project in ["project 1", "project 2"] {
+                build_command()?
+                    .arg("project")
+                    .arg("add")
+                    .arg(project)
+                    .assert()
+                    .success();
+            }
+
+            for criterion in ["Fun", "Useful"] {
+                build_command()?
+                    .arg("criterion")
+                    .arg("add")
+                    .arg(criterion)
+                    .assert()
+                    .success();
+            }
+
+            build_command()?
+                .arg("weight")
+                .arg("get")
+                .arg("project 2")
+                .arg("Useful")
+                .assert()
+                .success()
+                .stdout(diff("unrated, scored as 0\n"));
+
+            build_command()?
+                .arg("weight")
+                .arg("set")
+                .arg("2")
+                .arg("Useful")
+                .arg("3")
+                .assert()
+                .success();
+
+            build_command()?
+                .arg("weight")
+                .arg("set")
+                .arg("project 1")
+                .arg("1")
+                .arg("-2")
+                .assert()
+                .success();
+
+            build_command()?
+                .arg("weight")
+                .arg("get")
+                .arg("project 2")
+                .arg("2")
+                .assert()
+                .success()
+                .stdout(diff("3\n"));
+
+            build_command()?
+                .arg("weight")
+                .arg("get")
+                .arg("1")
+                .arg("Fun")
+                .assert()
+                .success()
+                .stdout(diff("-2\n"));
+
+            Ok(())
+        },
+    )?;
+
+    Ok(())
+}
+
+#[test]
+fn test_weight_unknown_reference() -> Result<()> {
+    let temp_dir = create_test_config_dir()?;
+    let temp_dir_path = temp_dir.path();
+    let workspace_file = NamedTempFile::new()?;
+    fs::remove_file(workspace_file.path())?;
+    with_vars(
+        [(
+            TEST_CONFIG_DIR,
+            Some(temp_dir_path.as_os_str().to_str().unwrap()),
+        )],
+        || {
+            build_command()?
+                .arg("workspace")
+                .arg("new")
+                .arg(workspace_file.path())
+                .assert()
+                .success();
+
+            build_command()?
+                .arg("project")
+                .arg("add")
+                .arg("project 1")
+                .assert()
+                .success();
+
+            build_command()?
+                .arg("criterion")
+                .arg("add")
+                .arg("Fun")
+                .assert()
+                .success();
+
+            build_command()?
+                .arg("weight")
+                .arg("set")
+                .arg("2")
+                .arg("Fun")
+                .arg("1")
+                .assert()
+                .failure()
+                .stderr(contains("no project 2"));
+
+            build_command()?
+                .arg("weight")
+                .arg("get")
+                .arg("project 1")
+                .arg("Funn")
+                .assert()
+                .failure()
+                .stderr(contains("no criterion Funn"));
+
+            build_command()?
+                .arg("weight")
+                .arg("set")
+                .arg("project 1")
+                .arg("Fun")
+                .arg("high")
+                .assert()
+                .failure();
+
+            Ok(())
+        },
+    )?;
+
+    Ok(())
+}
+
+#[test]
+fn test_weight_prefix_reference() -> Result<()> {
+    let temp_dir = create_test_config_dir()?;
+    let temp_dir_path = temp_dir.path();
+    let workspace_file = NamedTempFile::new()?;
+    fs::remove_file(workspace_file.path())?;
+    with_vars(
+        [(
+            TEST_CONFIG_DIR,
+            Some(temp_dir_path.as_os_str().to_str().unwrap()),
+        )],
+        || {
+            build_command()?
+                .arg("workspace")
+                .arg("new")
+                .arg(workspace_file.path())
+                .assert()
+                .success();
+
+            for project in ["Garden", "Gaming", "Learn Rust"] {
+                build_command()?
+                    .arg("project")
+                    .arg("add")
+                    .arg(project)
+                    .assert()
+                    .success();
+            }
+
+            build_command()?
+                .arg("criterion")
+                .arg("add")
+                .arg("Fun")
+                .assert()
+                .success();
+
+            build_command()?
+                .arg("w")
+                .arg("s")
+                .arg("3")
+                .arg("fun")
+                .arg("2")
+                .assert()
+                .success();
+
+            build_command()?
+                .arg("weight")
+                .arg("get")
+                .arg("learn")
+                .arg("Fun")
+                .assert()
+                .success()
+                .stdout(diff("2\n"));
+
+            build_command()?
+                .arg("weight")
+                .arg("get")
+                .arg("ga")
+                .arg("Fun")
+                .assert()
+                .failure()
+                .stderr(contains("ambiguous").and(contains("'Garden', 'Gaming'")));
+
+            Ok(())
+        },
+    )?;
+
+    Ok(())
+}
+
+#[test]
+fn test_uncertain_weights() -> Result<()> {
+    let temp_dir = create_test_config_dir()?;
+    let temp_dir_path = temp_dir.path();
+    let workspace_file = NamedTempFile::new()?;
+    fs::remove_file(workspace_file.path())?;
+    with_vars(
+        [(
+            TEST_CONFIG_DIR,
+            Some(temp_dir_path.as_os_str().to_str().unwrap()),
+        )],
+        || {
+            build_command()?
+                .arg("workspace")
+                .arg("new")
+                .arg(workspace_file.path())
+                .assert()
+                .success();
+
+            build_command()?
+                .arg("project")
+                .arg("add")
+                .arg("project 1")
+                .assert()
+                .success();
+
+            for criterion in ["Fun", "Useful", "Cheap"] {
+                build_command()?
+                    .arg("criterion")
+                    .arg("add")
+                    .arg(criterion)
+                    .assert()
+                    .success();
+            }
+
+            build_command()?
+                .arg("priority-set")
+                .arg("add")
+                .arg("Workday")
+                .assert()
+                .success();
+
+            for (criterion, weight) in [("Fun", "2.5"), ("Useful", "-1..2"), ("Cheap", "1..2..6")] {
+                build_command()?
+                    .arg("weight")
+                    .arg("set")
+                    .arg("project 1")
+                    .arg(criterion)
+                    .arg(weight)
+                    .assert()
+                    .success();
+
+                build_command()?
+                    .arg("weight")
+                    .arg("get")
+                    .arg("project 1")
+                    .arg(criterion)
+                    .assert()
+                    .success()
+                    .stdout(diff(format!("{weight}\n")));
+            }
+
+            build_command()?
+                .arg("weight")
+                .arg("set")
+                .arg("project 1")
+                .arg("Fun")
+                .arg("3..1")
+                .assert()
+                .failure();
+
+            build_command()?
+                .arg("display")
+                .arg("--ascii")
+                .assert()
+                .success()
+                .stdout(contains("| Worst | Best | Score |"))
+                .stdout(contains(
+                    "| 2.5 |  -1..2 | 1..2..6 |   2.5 | 10.5 |     6 |",
+                ));
+
+            Ok(())
+        },
+    )?;
+
+    Ok(())
+}
+
+#[test]
+fn test_set_weight_while_locked() -> Result<()> {
+    let temp_dir = create_test_config_dir()?;
+    let temp_dir_path = temp_dir.path();
+    let workspace_file = NamedTempFile::new()?;
+    fs::remove_file(workspace_file.path())?;
+    with_vars(
+        [(
+            TEST_CONFIG_DIR,
+            Some(temp_dir_path.as_os_str().to_str().unwrap()),
+        )],
+        || {
+            build_command()?
+                .arg("workspace")
+                .arg("new")
+                .arg(workspace_file.path())
+                .assert()
+                .success();
+
+            build_command()?
+                .args(["project", "add", "project 1"])
+                .assert()
+                .success();
+
+            build_command()?
+                .args(["criterion", "add", "Fun"])
+                .assert()
+                .success();
+
+            let config_file = temp_dir_path.join("recision").join("config.toml");
+            let config = fs::read_to_string(&config_file)?;
+            fs::write(
+                &config_file,
+                config.replace("lock_timeout = 10.0", "lock_timeout = 0.2"),
+            )?;
+
+            let mut lock_path = workspace_file.path().as_os_str().to_owned();
+            lock_path.push(".lock");
+            let mut lock = fs::File::create(&lock_path)?;
+            lock.lock()?;
+            lock.write_all(b"4242\nrecision weight set project 1 Fun 3\n")?;
+
+            build_command()?
+                .args(["weight", "set", "project 1", "Fun", "5"])
+                .assert()
+                .failure()
+                .stderr(contains(
+                    "is locked by process 4242 (recision weight set project 1 Fun 3)",
+                ));
+
+            lock.unlock()?;
+
+            build_command()?
+                .args(["weight", "set", "project 1", "Fun", "5"])
+                .assert()
+                .success();
+
+            build_command()?
+                .args(["weight", "get", "project 1", "Fun"])
+                .assert()
+                .success()
+                .stdout(diff("5\n"));
+
+            fs::remove_file(&lock_path)?;
+
+            Ok(())
+        },
+    )?;
+
+    Ok(())
+}
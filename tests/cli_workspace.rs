@@ -161,3 +161,63 @@ fn test_workspace_activate() -> Result<()> {
 
     Ok(())
 }
+
+#[test]
+fn test_workspace_migrate() -> Result<()> {
+    let temp_dir = tempdir()?;
+    let workspace_file = NamedTempFile::new()?;
+    let unversioned = "projects = []\ncriteria = []\npriority_sets = []\n";
+    fs::write(workspace_file.path(), unversioned)?;
+    with_vars(
+        [(
+            TEST_CONFIG_DIR,
+            Some(temp_dir.path().as_os_str().to_str().unwrap()),
+        )],
+        || {
+            build_command()?
+                .arg("workspace")
+                .arg("activate")
+                .arg(workspace_file.path())
+                .assert()
+                .success();
+
+            build_command()?
+                .args(["workspace", "migrate", "--check"])
+                .assert()
+                .success()
+                .stdout(contains(concat!(
+                    "Workspace has schema version 0, the current schema version is 2\n",
+                    "  0 -> 1: record the schema version, the scoring method and the project statuses in the file\n",
+                    "  1 -> 2: allow score snapshots, which older versions of recision can't keep\n",
+                    "Check only, the workspace is left unchanged\n",
+                )));
+            assert_eq!(unversioned, fs::read_to_string(workspace_file.path())?);
+
+            build_command()?
+                .args(["workspace", "migrate"])
+                .assert()
+                .success()
+                .stdout(contains("Upgraded workspace to schema version 2"));
+            assert!(fs::read_to_string(workspace_file.path())?.starts_with("schema_version = 2\n"));
+
+            build_command()?
+                .args(["workspace", "migrate", "--check"])
+                .assert()
+                .success()
+                .stdout(contains("Workspace is up to date with schema version 2"));
+
+            fs::write(workspace_file.path(), "schema_version = 3\n")?;
+            build_command()?
+                .args(["project", "list"])
+                .assert()
+                .failure()
+                .stderr(contains(
+                    "workspace has schema version 3, but this version of recision only supports up to 2",
+                ));
+
+            Ok(())
+        },
+    )?;
+
+    Ok(())
+}